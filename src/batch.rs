@@ -0,0 +1,80 @@
+/******************************************************************************/
+/* Batch: solve every registered day at once, in parallel                     */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+extern crate rayon;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::input::Source;
+use crate::registry::Entry;
+use crate::solution::{Part, Solved};
+use rayon::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// DayRun: the outcome of solving one day as part of a batch.
+// input is None if the day's input couldn't be read, in which case result
+//   holds that error.
+pub struct DayRun {
+    pub entry: &'static Entry,
+    pub input: Option<String>,
+    pub result: Result<Solved>,
+    // wall-clock time for the whole day, including reading its input
+    pub time: Duration,
+}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Read one day's input from inputs_dir and solve it.
+// A panic while parsing is caught and reported as an error, so it only
+//   affects this day. Puzzle::solve already catches panics in each part, so
+//   that they only affect that part.
+fn run_day(entry: &'static Entry, inputs_dir: &Path, parts: &[Part]) -> DayRun {
+    let start = Instant::now();
+    let source = Source::for_day(inputs_dir, entry.puzzle.day());
+
+    let (input, result) = match source.load() {
+        Ok(input) => {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| entry.puzzle.solve(&input, parts)))
+                .unwrap_or_else(|payload| Err(Error::from_panic(payload)))
+                .map_err(|err| source.locate(err));
+            (Some(input), result)
+        }
+        Err(err) => (None, Err(err)),
+    };
+
+    DayRun { entry, input, result, time: start.elapsed() }
+}
+
+// Solve the given parts of every entry, reading each day's input from
+//   inputs_dir. Days are solved in parallel on a pool of jobs threads, or
+//   one per CPU if jobs is None.
+// Returns one DayRun per entry, in the same order as entries.
+pub fn run_all(
+    entries: &'static [Entry],
+    inputs_dir: &Path,
+    parts: &[Part],
+    jobs: Option<usize>,
+) -> Result<Vec<DayRun>> {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(jobs) = jobs {
+        builder = builder.num_threads(jobs);
+    }
+    let pool = builder.build().map_err(|err| {
+        Error::new(ErrorKind::Io, format!("couldn't start thread pool: {}", err))
+    })?;
+
+    Ok(pool.install(|| {
+        entries
+            .par_iter()
+            .map(|entry| run_day(entry, inputs_dir, parts))
+            .collect()
+    }))
+}
@@ -0,0 +1,220 @@
+/******************************************************************************/
+/* Bench: timing statistics for each phase of a puzzle, and baselines to      */
+/*   compare them against                                                     */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+extern crate serde;
+extern crate serde_json;
+
+use crate::error::{Error, Result};
+use crate::output;
+use crate::solution::Part;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// Phase: the separately-timed steps of solving a puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl From<Part> for Phase {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Phase::Part1,
+            Part::Two => Phase::Part2,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+// Stats: summary statistics of one phase's samples, all in nanoseconds
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: f64,
+    pub median_ns: f64,
+    pub mean_ns: f64,
+    pub stddev_ns: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhaseStats {
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+// BenchReport: the statistics for every phase of one day
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub day: u32,
+    pub title: String,
+    pub iterations: usize,
+    pub phases: Vec<PhaseStats>,
+}
+
+// Comparison: how one phase's median changed relative to a baseline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub phase: Phase,
+    pub baseline_ns: f64,
+    pub current_ns: f64,
+}
+
+impl Stats {
+    // Compute statistics from a set of samples. Returns all zeroes if there
+    //   are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Stats { min_ns: 0.0, median_ns: 0.0, mean_ns: 0.0, stddev_ns: 0.0 };
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let count = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+
+        // the median of an even number of samples is the mean of the middle two
+        let mid = nanos.len() / 2;
+        let median = if nanos.len().is_multiple_of(2) {
+            (nanos[mid - 1] + nanos[mid]) / 2.0
+        } else {
+            nanos[mid]
+        };
+
+        // sample standard deviation; a single sample has no spread
+        let stddev = if nanos.len() > 1 {
+            let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / (count - 1.0);
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        Stats { min_ns: nanos[0], median_ns: median, mean_ns: mean, stddev_ns: stddev }
+    }
+}
+
+impl BenchReport {
+    pub fn from_samples(
+        day: u32,
+        title: &str,
+        iterations: usize,
+        samples: Vec<(Phase, Vec<Duration>)>,
+    ) -> Self {
+        BenchReport {
+            day,
+            title: title.to_string(),
+            iterations,
+            phases: samples
+                .iter()
+                .map(|(phase, durations)| PhaseStats {
+                    phase: *phase,
+                    stats: Stats::from_samples(durations),
+                })
+                .collect(),
+        }
+    }
+
+    // Compare each phase's median against the same day and phase in baseline.
+    // Phases missing from the baseline are skipped.
+    pub fn compare(&self, baseline: &[BenchReport]) -> Vec<Comparison> {
+        let baseline_report = match baseline.iter().find(|report| report.day == self.day) {
+            Some(report) => report,
+            None => return Vec::new(),
+        };
+
+        self.phases
+            .iter()
+            .filter_map(|current| {
+                baseline_report
+                    .phases
+                    .iter()
+                    .find(|base| base.phase == current.phase)
+                    .map(|base| Comparison {
+                        phase: current.phase,
+                        baseline_ns: base.stats.median_ns,
+                        current_ns: current.stats.median_ns,
+                    })
+            })
+            .collect()
+    }
+}
+
+impl Comparison {
+    // Change from the baseline as a percentage; positive means slower
+    pub fn percent_change(&self) -> f64 {
+        if self.baseline_ns == 0.0 {
+            return 0.0;
+        }
+        (self.current_ns - self.baseline_ns) / self.baseline_ns * 100.0
+    }
+}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Format a number of nanoseconds with a sensible unit, e.g. "12.34µs"
+pub fn format_nanos(nanos: f64) -> String {
+    if nanos < 1_000.0 {
+        format!("{:.0}ns", nanos)
+    } else if nanos < 1_000_000.0 {
+        format!("{:.2}µs", nanos / 1_000.0)
+    } else if nanos < 1_000_000_000.0 {
+        format!("{:.2}ms", nanos / 1_000_000.0)
+    } else {
+        format!("{:.2}s", nanos / 1_000_000_000.0)
+    }
+}
+
+// Serialize reports as pretty-printed JSON
+pub fn to_json(reports: &[BenchReport]) -> String {
+    output::to_pretty_json(reports)
+}
+
+// Load a baseline file written by save_baseline
+pub fn load_baseline(path: &Path) -> Result<Vec<BenchReport>> {
+    let contents = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+    serde_json::from_str(&contents).map_err(|err| {
+        Error::parse(format!("invalid baseline: {}", err))
+            .at(err.line(), err.column())
+            .in_file(path)
+    })
+}
+
+// Save reports to a baseline file. Days already in the file but not in
+//   reports are kept, so a baseline can be built up one day at a time.
+pub fn save_baseline(path: &Path, reports: &[BenchReport]) -> Result<()> {
+    let mut baseline = if path.exists() {
+        load_baseline(path)?
+    } else {
+        Vec::new()
+    };
+
+    for report in reports.iter() {
+        baseline.retain(|existing| existing.day != report.day);
+        baseline.push(report.clone());
+    }
+    baseline.sort_by_key(|report| report.day);
+
+    fs::write(path, to_json(&baseline)).map_err(|err| Error::io(path, err))
+}
@@ -0,0 +1,716 @@
+#[macro_use]
+extern crate clap;
+
+use advent_of_code2020::batch::{self, DayRun};
+use advent_of_code2020::bench::{self, format_nanos, BenchReport};
+use advent_of_code2020::verify::{self, ExpectedAnswers, Verdict};
+use advent_of_code2020::image::ImageFormat;
+use advent_of_code2020::input::{self, Source};
+use advent_of_code2020::output::{self, Format, Record};
+use advent_of_code2020::password_philosophy::{self, ParseOptions, PolicyKind, RangeCheck};
+use advent_of_code2020::report_repair::{self, Amount, Combination};
+use advent_of_code2020::toboggan_trajectory::{self, RaggedRows, Slope, WalkMode};
+use advent_of_code2020::{registry, Answer, Entry, Error, ErrorKind, Part, PartAnswer, Result};
+use clap::{App, ArgMatches};
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::time::Instant;
+
+// the type expense report entries are read as by the expenses subcommand
+#[cfg(feature = "bigint")]
+type ExpenseAmount = report_repair::BigInt;
+#[cfg(not(feature = "bigint"))]
+type ExpenseAmount = i64;
+
+// print the answer to one part of a puzzle
+fn print_answer(part: Part, answer: &Result<Answer>, time: &str) {
+    match answer {
+        Ok(answer) => println!("Part {}: {} ({})", part, answer, time),
+        Err(err)   => println!("Part {}: no answer ({})", part, err),
+    }
+}
+
+// print an error and exit with the exit code for its kind
+fn fail(err: &Error) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(err.exit_code());
+}
+
+// list every registered day
+fn list() {
+    for entry in registry::REGISTRY.iter() {
+        println!(
+            "day{:02}  {:<10} {}",
+            entry.puzzle.day(),
+            entry.nickname,
+            entry.puzzle.title()
+        );
+    }
+}
+
+// look up the program named on the command line, exiting if there's no such
+//   program
+fn lookup_program(matches: &ArgMatches) -> &'static Entry {
+    // Calling .unwrap() is safe here because "program" is required
+    let program = matches.value_of("program").unwrap();
+    match registry::lookup(program) {
+        Some(entry) => entry,
+        None => {
+            eprintln!("Bad program {}! Use `list` to see every program.", program);
+            process::exit(1);
+        }
+    }
+}
+
+// get the parts selected on the command line
+fn selected_parts(matches: &ArgMatches) -> Vec<Part> {
+    // "part" has a default value, and clap has already checked that it's one
+    //   of the possible values
+    Part::parse_selection(matches.value_of("part").unwrap()).unwrap()
+}
+
+// read the input selected on the command line for a day, exiting if it
+//   can't be read
+// returns where the input came from and its contents
+fn read_input(matches: &ArgMatches, entry: &Entry) -> (Source, String) {
+    let source = Source::from_args(
+        matches.value_of("input"),
+        matches.value_of("text"),
+        entry.puzzle.day(),
+    );
+    match source.load() {
+        Ok(contents) => (source, contents),
+        Err(err) => fail(&err),
+    }
+}
+
+// get the output format selected on the command line
+fn selected_format(matches: &ArgMatches) -> Format {
+    // "format" has a default value, and clap has already checked that it's
+    //   one of the possible values
+    matches.value_of("format").unwrap().parse().unwrap()
+}
+
+// print records in a machine-readable format
+fn print_records(format: Format, records: &[Record]) {
+    match format {
+        Format::Json => println!("{}", output::to_json(records)),
+        Format::Tsv  => print!("{}", output::to_tsv(records)),
+        Format::Text => unreachable!("text output isn't record-based"),
+    }
+}
+
+// solve one day and print its answers
+fn run(matches: &ArgMatches) {
+    let entry = lookup_program(matches);
+    let parts = selected_parts(matches);
+    let format = selected_format(matches);
+    let (source, contents) = read_input(matches, entry);
+
+    let solved = entry.puzzle.solve(&contents, &parts).map_err(|err| source.locate(err));
+
+    let solved = if format == Format::Text {
+        let solved = match solved {
+            Ok(solved) => solved,
+            Err(err) => fail(&err),
+        };
+
+        println!("Day {}: {}", entry.puzzle.day(), entry.puzzle.title());
+        println!("Parse: {}", format_nanos(solved.parse_time.as_nanos() as f64));
+        for answer in solved.answers.iter() {
+            print_answer(answer.part, &answer.answer, &format_nanos(answer.time.as_nanos() as f64));
+        }
+        solved
+    } else {
+        // every part gets a record even if the input couldn't be parsed, so
+        //   the error ends up in the output rather than only on stderr
+        let records = output::records(
+            entry.puzzle.day(),
+            entry.puzzle.title(),
+            Some(&contents),
+            &parts,
+            &solved,
+        );
+        print_records(format, &records);
+
+        match solved {
+            Ok(solved) => solved,
+            Err(err) => process::exit(err.exit_code()),
+        }
+    };
+
+    // if any part had no answer, exit with that part's error code
+    for answer in solved.answers.iter() {
+        if let Err(err) = &answer.answer {
+            process::exit(err.exit_code());
+        }
+    }
+}
+
+// the cell for one part in the summary table of the all subcommand
+fn summary_cell(run: &DayRun, parts: &[Part], part: Part) -> String {
+    if !parts.contains(&part) {
+        return "-".to_string();
+    }
+
+    match &run.result {
+        Ok(solved) => match solved.get(part) {
+            Some(PartAnswer { answer: Ok(answer), .. }) => answer.to_string(),
+            Some(PartAnswer { answer: Err(err), .. }) if err.kind == ErrorKind::Panic => {
+                "PANIC".to_string()
+            }
+            _ => "ERROR".to_string(),
+        },
+        Err(err) if err.kind == ErrorKind::Panic => "PANIC".to_string(),
+        Err(_) => "ERROR".to_string(),
+    }
+}
+
+// print the results of the all subcommand as a table, followed by the
+//   details of anything that went wrong
+fn print_summary(runs: &[DayRun], parts: &[Part]) {
+    println!("{:>3}  {:<24} {:>16} {:>16} {:>10}", "day", "title", "part 1", "part 2", "time");
+    for run in runs.iter() {
+        println!(
+            "{:>3}  {:<24} {:>16} {:>16} {:>10}",
+            run.entry.puzzle.day(),
+            run.entry.puzzle.title(),
+            summary_cell(run, parts, Part::One),
+            summary_cell(run, parts, Part::Two),
+            format_nanos(run.time.as_nanos() as f64)
+        );
+    }
+
+    for run in runs.iter() {
+        let day = run.entry.puzzle.day();
+        match &run.result {
+            Ok(solved) => {
+                for answer in solved.answers.iter() {
+                    if let Err(err) = &answer.answer {
+                        println!("day{:02} part{}: {}", day, answer.part, err);
+                    }
+                }
+            }
+            Err(err) => println!("day{:02}: {}", day, err),
+        }
+    }
+}
+
+// solve every registered day in parallel and print a summary
+fn all(matches: &ArgMatches) {
+    let parts = selected_parts(matches);
+    let format = selected_format(matches);
+    // "inputs" has a default value, so unwrapping is safe
+    let inputs_dir = Path::new(matches.value_of("inputs").unwrap());
+    let jobs = matches.value_of("jobs").map(|_| match value_t!(matches, "jobs", usize) {
+        Ok(jobs) if jobs > 0 => jobs,
+        _ => {
+            eprintln!("--jobs must be a positive number");
+            process::exit(1);
+        }
+    });
+
+    let start = Instant::now();
+    let runs = match batch::run_all(&registry::REGISTRY, inputs_dir, &parts, jobs) {
+        Ok(runs) => runs,
+        Err(err) => fail(&err),
+    };
+    let elapsed = start.elapsed();
+
+    if format == Format::Text {
+        print_summary(&runs, &parts);
+        println!("Total: {}", format_nanos(elapsed.as_nanos() as f64));
+    } else {
+        let records: Vec<Record> = runs
+            .iter()
+            .flat_map(|run| {
+                output::records(
+                    run.entry.puzzle.day(),
+                    run.entry.puzzle.title(),
+                    run.input.as_deref(),
+                    &parts,
+                    &run.result,
+                )
+            })
+            .collect();
+        print_records(format, &records);
+    }
+
+    // exit with the code of the first thing that went wrong, if anything did
+    for run in runs.iter() {
+        match &run.result {
+            Ok(solved) => {
+                for answer in solved.answers.iter() {
+                    if let Err(err) = &answer.answer {
+                        process::exit(err.exit_code());
+                    }
+                }
+            }
+            Err(err) => process::exit(err.exit_code()),
+        }
+    }
+}
+
+// print the entries of a combination under heading, with their line numbers
+//   and product
+fn print_combination(combination: &Combination<ExpenseAmount>, heading: &str) {
+    println!("{}:", heading);
+    for (entry, ix) in combination.entries.iter().zip(combination.indices.iter()) {
+        println!("  line {:>4}: {}", ix + 1, entry);
+    }
+    match combination.product() {
+        Some(product) => println!("Product: {}", product),
+        None => println!("Product: doesn't fit in 64 bits; build with --features bigint"),
+    }
+}
+
+// find expense report entries that sum to a target, and print them along
+//   with their line numbers and product
+fn expenses(matches: &ArgMatches) {
+    // day 1 is always registered
+    let entry = registry::lookup("report").unwrap();
+    let source = Source::from_args(
+        matches.value_of("input"),
+        matches.value_of("text"),
+        entry.puzzle.day(),
+    );
+
+    // both of these have default values, so they're always present; they
+    //   just might not be numbers
+    let target = match matches.value_of("target").unwrap().parse::<ExpenseAmount>() {
+        Ok(target) => target,
+        Err(_) => {
+            eprintln!("--target must be a whole number");
+            process::exit(1);
+        }
+    };
+    let count = match value_t!(matches, "count", usize) {
+        Ok(count) => count,
+        Err(_) => {
+            eprintln!("--count must be a non-negative whole number");
+            process::exit(1);
+        }
+    };
+
+    // read the entries a line at a time, since expense reports can be
+    //   huge, and keep them in input order so the indices are line numbers
+    let entries: Vec<ExpenseAmount> = match source
+        .open()
+        .and_then(|reader| input::read_integers(reader, &source))
+    {
+        Ok(entries) => entries,
+        Err(err) => fail(&source.locate(err)),
+    };
+
+    if matches.is_present("subset") {
+        let target_text = target.to_string();
+        match report_repair::subset_sum(&entries, target) {
+            Ok(Some(combination)) => print_combination(
+                &combination,
+                &format!("{} entries sum to {}", combination.entries.len(), target_text),
+            ),
+            Ok(None) => fail(&source.locate(Error::no_solution(format!(
+                "no entries sum to {}",
+                target_text
+            )))),
+            Err(err) => fail(&err),
+        }
+        return;
+    }
+
+    if matches.is_present("closest") {
+        let target_sum = target.to_sum();
+        match report_repair::closest_sum(&entries, target, count) {
+            Some(combination) => {
+                let sum = combination.sum();
+                let heading = format!("{} entries sum to {}", count, sum);
+                let heading = if sum > target_sum {
+                    format!("{}, {} over the target", heading, sum - target_sum)
+                } else if sum < target_sum {
+                    format!("{}, {} under the target", heading, target_sum - sum)
+                } else {
+                    heading
+                };
+                print_combination(&combination, &heading);
+            }
+            None => fail(&source.locate(Error::no_solution(format!(
+                "the report has fewer than {} entries",
+                count
+            )))),
+        }
+        return;
+    }
+
+    let allow_reuse = matches.is_present("reuse");
+
+    if matches.is_present("total") {
+        println!("{}", report_repair::count_sums(&entries, target, count, allow_reuse));
+        return;
+    }
+
+    let target_text = target.to_string();
+    let mut found = 0;
+    report_repair::for_each_sum(&entries, target, count, allow_reuse, |combination| {
+        found += 1;
+        if found > 1 {
+            println!();
+        }
+        print_combination(&combination, &format!("{} entries sum to {}", count, target_text));
+        matches.is_present("all")
+    });
+
+    if found == 0 {
+        fail(&source.locate(Error::no_solution(format!(
+            "no {} entries sum to {}",
+            count, target_text
+        ))));
+    }
+}
+
+// check the passwords in a password database against each selected policy,
+//   in one pass over the database. Prints either how many passwords each
+//   policy accepts, or every line's verdict and the reason for it.
+fn audit(matches: &ArgMatches) {
+    // day 2 is always registered
+    let entry = registry::lookup("password").unwrap();
+    let source = Source::from_args(
+        matches.value_of("input"),
+        matches.value_of("text"),
+        entry.puzzle.day(),
+    );
+
+    // clap has already checked these are valid policy names
+    let kinds: Vec<PolicyKind> = match matches.values_of("policy") {
+        Some(names) => names.map(|name| name.parse().unwrap()).collect(),
+        None => PolicyKind::ALL.to_vec(),
+    };
+
+    let options = ParseOptions {
+        range_check: if matches.is_present("lenient") {
+            RangeCheck::Lenient
+        } else {
+            RangeCheck::Strict
+        },
+        // index has a default value, and clap has checked it's valid
+        indexing: matches.value_of("index").unwrap().parse().unwrap(),
+    };
+
+    let input_lines = match source
+        .load()
+        .and_then(|contents| password_philosophy::parse_input_with(&contents, options))
+    {
+        Ok(input_lines) => input_lines,
+        Err(err) => fail(&source.locate(err)),
+    };
+
+    // reading a line as a kind of policy can fail, e.g. if its numbers are
+    //   past the end of its password when read as positions
+    let audit = || {
+        password_philosophy::audit(&input_lines, &kinds)
+            .unwrap_or_else(|err| fail(&source.locate(err)))
+    };
+
+    // format has a default value, and clap has checked it's one of these
+    match matches.value_of("format").unwrap() {
+        "csv" => print!("{}", password_philosophy::audit_to_csv(&audit())),
+        "json" => println!("{}", password_philosophy::audit_to_json(&audit())),
+        _ => {
+            let counts = password_philosophy::count_valid(&input_lines, &kinds)
+                .unwrap_or_else(|err| fail(&source.locate(err)));
+            for (kind, count) in kinds.iter().zip(counts.iter()) {
+                println!("{:<8} {}", kind.name(), count);
+            }
+        }
+    }
+}
+
+// count the trees hit on a toboggan map along each slope given, print a
+//   table of them along with their product, and draw their paths if asked;
+//   or, with --search, rank every slope within bounds by the trees they hit
+fn slopes(matches: &ArgMatches) {
+    // day 3 is always registered
+    let entry = registry::lookup("toboggan").unwrap();
+    let source = Source::from_args(
+        matches.value_of("input"),
+        matches.value_of("text"),
+        entry.puzzle.day(),
+    );
+
+    // slopes from the file come first, then those given one at a time
+    let mut slopes: Vec<Slope> = Vec::new();
+    if let Some(path) = matches.value_of("slope-file") {
+        let slope_source = Source::File(path.into());
+        match slope_source.load().and_then(|contents| toboggan_trajectory::parse_slopes(&contents)) {
+            Ok(file_slopes) => slopes.extend(file_slopes),
+            Err(err) => fail(&slope_source.locate(err)),
+        }
+    }
+    for text in matches.values_of("slope").into_iter().flatten() {
+        match text.parse::<Slope>() {
+            Ok(slope) => slopes.push(slope),
+            Err(err) => {
+                eprintln!("--slope: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+    if slopes.is_empty() {
+        slopes = toboggan_trajectory::PART2_SLOPES.to_vec();
+    }
+
+    // the bounds to search, written like a slope
+    let search = match matches.value_of("search").map(|text| text.parse::<Slope>()) {
+        Some(Ok(bounds)) if bounds.right() >= 1 => Some(bounds),
+        Some(_) => {
+            eprintln!("--search must be <right>,<down>, with both at least 1");
+            process::exit(1);
+        }
+        None => None,
+    };
+    // top has a default value, so it's always present
+    let top = match value_t!(matches, "top", usize) {
+        Ok(top) => top,
+        Err(_) => {
+            eprintln!("--top must be a non-negative whole number");
+            process::exit(1);
+        }
+    };
+    // so does scale
+    let scale = match value_t!(matches, "scale", usize) {
+        Ok(scale) if scale >= 1 => scale,
+        _ => {
+            eprintln!("--scale must be a whole number, at least 1");
+            process::exit(1);
+        }
+    };
+    let mode = WalkMode {
+        // edge has a default value, and clap has checked it's valid
+        edge: matches.value_of("edge").unwrap().parse().unwrap(),
+        vertical_wrap: matches.is_present("vertical-wrap"),
+    };
+    let ragged = if matches.is_present("pad") { RaggedRows::Pad } else { RaggedRows::Reject };
+
+    // check the format can be written before solving anything
+    let image_format = matches.value_of("image").map(|path| {
+        let format = ImageFormat::from_path(Path::new(path))
+            .and_then(|format| format.check_supported().map(|_| format));
+        match format {
+            Ok(format) => format,
+            Err(err) => {
+                eprintln!("--image: {}", err);
+                process::exit(err.exit_code());
+            }
+        }
+    });
+
+    let map = match source
+        .load()
+        .and_then(|contents| toboggan_trajectory::parse_input_with(&contents, ragged))
+    {
+        Ok(map) => map,
+        Err(err) => fail(&source.locate(err)),
+    };
+
+    if let Some(bounds) = search {
+        let ranked = toboggan_trajectory::rank_slopes(&map, bounds.right() as usize, bounds.down(), mode);
+        let mut ranked = match ranked {
+            Ok(ranked) => ranked,
+            Err(err) => {
+                eprintln!("--search: {}", err);
+                process::exit(1);
+            }
+        };
+        if matches.is_present("most") {
+            // reverse, keeping tied slopes in their original order
+            ranked.sort_by_key(|count| std::cmp::Reverse(count.trees));
+        }
+        println!("{:>6} {:>6} {:>6} {:>8}", "rank", "right", "down", "trees");
+        for (rank, count) in ranked.iter().take(top).enumerate() {
+            println!(
+                "{:>6} {:>6} {:>6} {:>8}",
+                rank + 1,
+                count.slope.right(),
+                count.slope.down(),
+                count.trees
+            );
+        }
+        return;
+    }
+
+    let counts = toboggan_trajectory::count_trees_along(&map, &slopes, mode);
+    println!("{:>6} {:>6} {:>8}", "right", "down", "trees");
+    for (slope, count) in slopes.iter().zip(counts.iter()) {
+        println!("{:>6} {:>6} {:>8}", slope.right(), slope.down(), count);
+    }
+    match toboggan_trajectory::tree_product(&counts) {
+        Some(product) => println!("Product: {}", product),
+        None => println!("Product: doesn't fit in 64 bits"),
+    }
+
+    if matches.is_present("draw") {
+        println!();
+        print!("{}", toboggan_trajectory::render_ascii(&map, &slopes, mode));
+    }
+    if let (Some(path), Some(format)) = (matches.value_of("image"), image_format) {
+        let written = toboggan_trajectory::render_image(&map, &slopes, mode, scale)
+            .encode(format)
+            .and_then(|bytes| fs::write(path, bytes).map_err(|err| Error::io(path, err)));
+        if let Err(err) = written {
+            fail(&err);
+        }
+    }
+}
+
+// print a bench report as a table, comparing against the baseline if there
+//   is one
+fn print_bench_report(report: &BenchReport, baseline: Option<&[BenchReport]>, threshold: f64) {
+    println!("Day {}: {} ({} iterations)", report.day, report.title, report.iterations);
+    println!(
+        "  {:<6} {:>10} {:>10} {:>10} {:>10}",
+        "phase", "min", "median", "mean", "stddev"
+    );
+
+    let comparisons = baseline.map(|baseline| report.compare(baseline)).unwrap_or_default();
+    for phase in report.phases.iter() {
+        let stats = &phase.stats;
+        print!(
+            "  {:<6} {:>10} {:>10} {:>10} {:>10}",
+            phase.phase.to_string(),
+            format_nanos(stats.min_ns),
+            format_nanos(stats.median_ns),
+            format_nanos(stats.mean_ns),
+            format_nanos(stats.stddev_ns)
+        );
+
+        if let Some(comparison) = comparisons.iter().find(|c| c.phase == phase.phase) {
+            let change = comparison.percent_change();
+            print!("  {:+.1}% vs baseline", change);
+            if change > threshold {
+                print!(" (regression)");
+            }
+        }
+        println!();
+    }
+}
+
+// benchmark one day and report timing statistics
+fn bench(matches: &ArgMatches) {
+    let entry = lookup_program(matches);
+    let parts = selected_parts(matches);
+    let (source, contents) = read_input(matches, entry);
+
+    // both of these have default values, so they're always present; they
+    //   just might not be numbers
+    let iterations = match value_t!(matches, "iterations", usize) {
+        Ok(iterations) if iterations > 0 => iterations,
+        _ => {
+            eprintln!("--iterations must be a positive number");
+            process::exit(1);
+        }
+    };
+    let threshold = match value_t!(matches, "threshold", f64) {
+        Ok(threshold) => threshold,
+        Err(_) => {
+            eprintln!("--threshold must be a number");
+            process::exit(1);
+        }
+    };
+
+    let report = match entry.puzzle.bench(&contents, &parts, iterations) {
+        Ok(report) => report,
+        Err(err) => fail(&source.locate(err)),
+    };
+
+    let baseline = matches.value_of("baseline").map(|path| {
+        match bench::load_baseline(Path::new(path)) {
+            Ok(baseline) => baseline,
+            Err(err) => fail(&err),
+        }
+    });
+
+    if matches.is_present("json") {
+        println!("{}", bench::to_json(std::slice::from_ref(&report)));
+    } else {
+        print_bench_report(&report, baseline.as_deref(), threshold);
+    }
+
+    if let Some(path) = matches.value_of("save-baseline") {
+        if let Err(err) = bench::save_baseline(Path::new(path), &[report]) {
+            fail(&err);
+        }
+    }
+}
+
+// check every registered day against the answers file, exiting non-zero if
+//   any part doesn't match
+fn verify(matches: &ArgMatches) {
+    let parts = selected_parts(matches);
+    // both of these have default values, so unwrapping is safe
+    let answers_path = Path::new(matches.value_of("answers").unwrap());
+    let inputs_dir = Path::new(matches.value_of("inputs").unwrap());
+
+    let expected = match ExpectedAnswers::load(answers_path) {
+        Ok(expected) => expected,
+        Err(err) => fail(&err),
+    };
+
+    let mut failures = 0;
+    for entry in registry::REGISTRY.iter() {
+        let day = entry.puzzle.day();
+        let source = Source::for_day(inputs_dir, day);
+
+        let verdicts = match source.load() {
+            Ok(contents) => verify::verify(entry.puzzle, &contents, &parts, &expected)
+                .into_iter()
+                .map(|(part, verdict)| match verdict {
+                    Verdict::Fail(err) => (part, Verdict::Fail(source.locate(err))),
+                    verdict => (part, verdict),
+                })
+                .collect(),
+            Err(err) => {
+                parts.iter().map(|part| (*part, Verdict::Fail(err.clone()))).collect::<Vec<_>>()
+            }
+        };
+
+        for (part, verdict) in verdicts.iter() {
+            let label = format!("day{:02} part{}", day, part);
+            match verdict {
+                Verdict::Pass => println!("{}  pass", label),
+                Verdict::Unknown(actual) => println!("{}  unknown (no expected answer; got {})", label, actual),
+                Verdict::Fail(err) => println!("{}  FAIL: {}", label, err),
+                Verdict::Mismatch { expected, actual } => {
+                    println!("{}  MISMATCH", label);
+                    println!("    - expected: {}", expected);
+                    println!("    + actual:   {}", actual);
+                }
+            }
+
+            if let Verdict::Fail(_) | Verdict::Mismatch { .. } = verdict {
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        println!("{} part(s) failed verification", failures);
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let yaml = load_yaml!("cli.yml");
+    let matches = App::from_yaml(yaml).get_matches();
+
+    match matches.subcommand() {
+        ("list", _) => list(),
+        ("all", Some(all_matches)) => all(all_matches),
+        ("expenses", Some(expenses_matches)) => expenses(expenses_matches),
+        ("audit", Some(audit_matches)) => audit(audit_matches),
+        ("slopes", Some(slopes_matches)) => slopes(slopes_matches),
+        ("bench", Some(bench_matches)) => bench(bench_matches),
+        ("verify", Some(verify_matches)) => verify(verify_matches),
+        // no subcommand; "program" and "input" are required in this case
+        _ => run(&matches),
+    }
+}
@@ -1,7 +1,7 @@
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::{Answer, Solution};
 use std::cmp::Ordering;
-use std::io::prelude::*;
-use std::fs::File;
-use std::process;
 
 // constant definitions
 // number of times we can partition rows and columns
@@ -9,21 +9,23 @@ const ROW_PARTITIONS: usize = 7;
 const COL_PARTITIONS: usize = 3;
 
 #[derive(Clone, Copy, Debug)]
-enum RowPartition {
+pub enum RowPartition {
     Front,
     Back,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum ColumnPartition {
+pub enum ColumnPartition {
     Left,
     Right,
 }
 
+// BoardingPass: the direction to take at every partition. Parsing checks a
+//   pass has all of them, so there's never one missing.
 #[derive(Debug)]
-struct BoardingPass {
-    row_directions: [Option<RowPartition>; ROW_PARTITIONS],
-    column_directions: [Option<ColumnPartition>; COL_PARTITIONS],
+pub struct BoardingPass {
+    pub row_directions: [RowPartition; ROW_PARTITIONS],
+    pub column_directions: [ColumnPartition; COL_PARTITIONS],
 }
 
 impl BoardingPass {
@@ -32,14 +34,14 @@ impl BoardingPass {
         // total number of rows on the plane
         let total_rows: u32 = 2_u32.pow(ROW_PARTITIONS as u32) - 1;
         // use binary partition search to get the actual row number
-        return self.get_row_recurse(0, 0, total_rows);
+        self.get_row_recurse(0, 0, total_rows)
     }
 
     // Get the column number based on this pass's column directions
     pub fn get_col(&self) -> u32 {
         // total number of columns on the plane
         let total_cols: u32 = 2_u32.pow(COL_PARTITIONS as u32) - 1;
-        return self.get_col_recurse(0, 0, total_cols);
+        self.get_col_recurse(0, 0, total_cols)
     }
 
 
@@ -53,44 +55,33 @@ impl BoardingPass {
             // if there is only a difference of 1 between max and min, we've
             //   reached the end of our recursion. use the last row direction to
             //   return the appropriate row number
-            1 => {
-                if let Some(dir) = self.row_directions[dir_ix] {
-                    match dir {
-                        RowPartition::Front => return min_row,
-                        RowPartition::Back  => return max_row,
-                    }
-                } else {
-                    println!("No direction for index {}", dir_ix);
-                    return 0;
-                }
+            1 => match self.row_directions[dir_ix] {
+                RowPartition::Front => min_row,
+                RowPartition::Back  => max_row,
             },
 
             // else, use the direction at this index to determine whether to go
             //  forward or backward
             _ => {
-                if let Some(dir) = self.row_directions[dir_ix] {
-                    // depending on the direction that we have to go in, we will
-                    //   be setting a new min/max row number to send to the next
-                    //   recursive call to this function.
-                    let mut new_min_row = min_row;
-                    let mut new_max_row = max_row;
-                    // we also need to advance to the next direction index.
-                    let new_dir_ix = dir_ix + 1;
-                    
-                    // calculate the midpoint between the two current rows.
-                    // if we go to the front, the midpoint is our new max row.
-                    // if we go to the back, the midpoint is our new min row.
-                    let midpoint = (min_row + max_row) / 2;
-                    match dir {
-                        RowPartition::Front => new_max_row = midpoint,
-                        RowPartition::Back  => new_min_row = midpoint + 1,
-                    }
-
-                    return self.get_row_recurse(new_dir_ix, new_min_row, new_max_row);
-                } else {
-                    println!("Error: got no direction for index {}!", dir_ix);
-                    return 0;
+                let dir = self.row_directions[dir_ix];
+                // depending on the direction that we have to go in, we will
+                //   be setting a new min/max row number to send to the next
+                //   recursive call to this function.
+                let mut new_min_row = min_row;
+                let mut new_max_row = max_row;
+                // we also need to advance to the next direction index.
+                let new_dir_ix = dir_ix + 1;
+                
+                // calculate the midpoint between the two current rows.
+                // if we go to the front, the midpoint is our new max row.
+                // if we go to the back, the midpoint is our new min row.
+                let midpoint = (min_row + max_row) / 2;
+                match dir {
+                    RowPartition::Front => new_max_row = midpoint,
+                    RowPartition::Back  => new_min_row = midpoint + 1,
                 }
+
+                self.get_row_recurse(new_dir_ix, new_min_row, new_max_row)
             }
         }
     }
@@ -100,53 +91,42 @@ impl BoardingPass {
             // if there is only a difference of 1 between max and min, we've
             //   reached the end of our recursion. use the last col direction to
             //   return the appropriate col number
-            1 => {
-                if let Some(dir) = self.column_directions[dir_ix] {
-                    match dir {
-                        ColumnPartition::Left  => return min_col,
-                        ColumnPartition::Right => return max_col,
-                    }
-                } else {
-                    println!("No direction for index {}", dir_ix);
-                    return 0;
-                }
+            1 => match self.column_directions[dir_ix] {
+                ColumnPartition::Left  => min_col,
+                ColumnPartition::Right => max_col,
             },
 
             // else, use the direction at this index to determine whether to go
             //  forward or backward
             _ => {
-                if let Some(dir) = self.column_directions[dir_ix] {
-                    // depending on the direction that we have to go in, we will
-                    //   be setting a new min/max col number to send to the next
-                    //   recursive call to this function.
-                    let mut new_min_col = min_col;
-                    let mut new_max_col = max_col;
-                    // we also need to advance to the next direction index.
-                    let new_dir_ix = dir_ix + 1;
-                    
-                    // calculate the midpoint between the two current cols.
-                    // if we go to the front, the midpoint is our new max col.
-                    // if we go to the back, the midpoint is our new min col.
-                    let midpoint = (min_col + max_col) / 2;
-                    match dir {
-                        ColumnPartition::Left  => new_max_col = midpoint,
-                        ColumnPartition::Right => new_min_col = midpoint + 1,
-                    }
-
-                    return self.get_col_recurse(new_dir_ix, new_min_col, new_max_col);
-                } else {
-                    println!("Error: got no direction for index {}!", dir_ix);
-                    return 0;
+                let dir = self.column_directions[dir_ix];
+                // depending on the direction that we have to go in, we will
+                //   be setting a new min/max col number to send to the next
+                //   recursive call to this function.
+                let mut new_min_col = min_col;
+                let mut new_max_col = max_col;
+                // we also need to advance to the next direction index.
+                let new_dir_ix = dir_ix + 1;
+                
+                // calculate the midpoint between the two current cols.
+                // if we go to the front, the midpoint is our new max col.
+                // if we go to the back, the midpoint is our new min col.
+                let midpoint = (min_col + max_col) / 2;
+                match dir {
+                    ColumnPartition::Left  => new_max_col = midpoint,
+                    ColumnPartition::Right => new_min_col = midpoint + 1,
                 }
+
+                self.get_col_recurse(new_dir_ix, new_min_col, new_max_col)
             }
         }
     }
 }
 
 #[derive(Eq)]
-struct Seat {
-    row: u32,
-    col: u32,
+pub struct Seat {
+    pub row: u32,
+    pub col: u32,
 }
 
 impl Seat {
@@ -174,14 +154,24 @@ impl Ord for Seat {
 }
 
 // Construct and return a vec of BoardingPass structs from the input string
-fn build_boarding_passes(input: &str) -> Vec<BoardingPass> {
+pub fn build_boarding_passes(input: &str) -> Result<Vec<BoardingPass>> {
     let mut passes: Vec<BoardingPass> = Vec::new();
     
-    for input_pass in input.lines() {
-        // initialize BoardingPass as empty
-        let mut pass = BoardingPass { 
-            row_directions: [None; ROW_PARTITIONS],
-            column_directions: [None; COL_PARTITIONS],
+    for line in input::lines(input) {
+        let (line_no, input_pass) = (line.number, line.text);
+        let pass_len = input_pass.chars().count();
+        if pass_len != ROW_PARTITIONS + COL_PARTITIONS {
+            return Err(Error::parse(format!(
+                "expected a boarding pass of {} characters, found {}",
+                ROW_PARTITIONS + COL_PARTITIONS, pass_len
+            )).at(line_no, 1));
+        }
+
+        // initialize BoardingPass with placeholders; the length check above
+        //   means every one of them gets overwritten below
+        let mut pass = BoardingPass {
+            row_directions: [RowPartition::Front; ROW_PARTITIONS],
+            column_directions: [ColumnPartition::Left; COL_PARTITIONS],
         };
 
         // an input pass is represented by ROW_PARTITIONS + COL_PARTITIONS chars
@@ -191,11 +181,11 @@ fn build_boarding_passes(input: &str) -> Vec<BoardingPass> {
             //   row instruction
             if ix < ROW_PARTITIONS {
                 pass.row_directions[ix] = match current_char {
-                    'F' => Some(RowPartition::Front),
-                    'B' => Some(RowPartition::Back),
+                    'F' => RowPartition::Front,
+                    'B' => RowPartition::Back,
                     _   => {
-                        println!("Error! Unexpected row char {}", current_char);
-                        process::exit(1);
+                        return Err(Error::parse(format!("unexpected row char {:?}", current_char))
+                            .at(line_no, ix + 1));
                     }
                 }
             } else {
@@ -203,11 +193,11 @@ fn build_boarding_passes(input: &str) -> Vec<BoardingPass> {
                 // the index of column directions should start at 0, not at
                 //   ROW_PARTITIONS like the input string does
                 pass.column_directions[ix - ROW_PARTITIONS] = match current_char {
-                    'L' => Some(ColumnPartition::Left),
-                    'R' => Some(ColumnPartition::Right),
+                    'L' => ColumnPartition::Left,
+                    'R' => ColumnPartition::Right,
                     _   => {
-                        println!("Error! Unexpected col char {}", current_char);
-                        process::exit(1);
+                        return Err(Error::parse(format!("unexpected col char {:?}", current_char))
+                            .at(line_no, ix + 1));
                     }
                 }
             }
@@ -216,11 +206,11 @@ fn build_boarding_passes(input: &str) -> Vec<BoardingPass> {
         passes.push(pass);
     }
 
-    return passes;
+    Ok(passes)
 }
 
 // Construct and return a vec of Seat structs from the input boarding pass vec
-fn build_seats(passes: &Vec<BoardingPass>) -> Vec<Seat> {
+pub fn build_seats(passes: &[BoardingPass]) -> Vec<Seat> {
     let mut seats: Vec<Seat> = Vec::new();
     for pass in passes.iter() {
         seats.push(Seat {
@@ -229,45 +219,84 @@ fn build_seats(passes: &Vec<BoardingPass>) -> Vec<Seat> {
         });
     }
 
-    return seats;
+    seats
 }
 
-pub fn run(input: &str) {
-    // todo: error handling for missing file
-    let mut file = File::open(input).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+pub struct BinaryBoarding;
 
-    let boarding_passes = build_boarding_passes(&contents);
-    let mut seats = build_seats(&boarding_passes);
-    
-    // sort the seats by seat ID
-    seats.sort_unstable();
-
-    // we have to find the missing seat.
-    // the first few seats are known to be missing, but we don't know exactly
-    //   how many
-    // so, start by examining the first seat in the vec, and try to find the
-    //   missing id from there
-    let mut current_seat_id = match seats.first() {
-        Some(seat) => seat.get_seat_id(),
-        None => {
-            println!("Error; no first seat found!");
-            0
-        },
-    };
-
-    println!("First seat ID: {}", current_seat_id);
-    
-    // start at index 1 because we already know the first seat ID
-    for seat_id in seats[1..].iter().map(|seat| seat.get_seat_id()) {
-        println!("Seat ID: {}", seat_id);
-        // if this seat isn't the current ID + 1, we know that currentID+1 is
-        //   the missing seat number
-        if seat_id != current_seat_id + 1 {
-            println!("My seat ID is {}", current_seat_id + 1);
-            break;
+impl Solution for BinaryBoarding {
+    type Input = Vec<BoardingPass>;
+
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Binary Boarding";
+
+    fn parse(&self, input: &str) -> Result<Vec<BoardingPass>> {
+        build_boarding_passes(input)
+    }
+
+    // part 1: the highest seat ID on any boarding pass
+    fn part1(&self, boarding_passes: &Vec<BoardingPass>) -> Result<Answer> {
+        match build_seats(boarding_passes).iter().max() {
+            Some(seat) => Ok(Answer::from(seat.get_seat_id())),
+            None => Err(Error::no_solution("no boarding passes")),
         }
-        current_seat_id = seat_id;
     }
-}
\ No newline at end of file
+
+    // part 2: my seat ID; the one missing from the middle of the list
+    fn part2(&self, boarding_passes: &Vec<BoardingPass>) -> Result<Answer> {
+        let mut seats = build_seats(boarding_passes);
+
+        // sort the seats by seat ID
+        seats.sort_unstable();
+
+        // we have to find the missing seat.
+        // the first few seats are known to be missing, but we don't know exactly
+        //   how many
+        // so, start by examining the first seat in the vec, and try to find the
+        //   missing id from there
+        let mut current_seat_id = match seats.first() {
+            Some(seat) => seat.get_seat_id(),
+            None => return Err(Error::no_solution("no boarding passes")),
+        };
+
+        // start at index 1 because we already know the first seat ID
+        for seat_id in seats[1..].iter().map(|seat| seat.get_seat_id()) {
+            // if this seat isn't the current ID + 1, we know that currentID+1 is
+            //   the missing seat number
+            if seat_id != current_seat_id + 1 {
+                return Ok(Answer::from(current_seat_id + 1));
+            }
+            current_seat_id = seat_id;
+        }
+
+        Err(Error::no_solution("no seat is missing"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_seats() {
+        let passes = build_boarding_passes(include_str!("../examples/day05/example.txt")).unwrap();
+        let seats: Vec<(u32, u32, u32)> = build_seats(&passes)
+            .iter()
+            .map(|seat| (seat.row, seat.col, seat.get_seat_id()))
+            .collect();
+        assert_eq!(seats, vec![(44, 5, 357), (70, 7, 567), (14, 7, 119), (102, 4, 820)]);
+    }
+
+    #[test]
+    fn missing_seat_between_neighbours() {
+        // seats 8, 9 and 11 (row 1, columns 0, 1 and 3), so 10 is ours
+        let passes = build_boarding_passes("FFFFFFBLLL\nFFFFFFBLLR\nFFFFFFBLRR\n").unwrap();
+        assert_eq!(BinaryBoarding.part2(&passes).unwrap(), Answer::Number(10));
+    }
+
+    #[test]
+    fn bad_character_is_located() {
+        let err = build_boarding_passes("FBFBBFFRLR\nFBFBXFFRLR\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(5)));
+    }
+}
@@ -1,11 +1,6 @@
-use std::io::prelude::*;
-use std::fs::File;
-
-// configure what a blank line looks like depending on OS
-#[cfg(windows)]
-const BLANK_LINE: &'static str = "\r\n\r\n";
-#[cfg(not(windows))]
-const BLANK_LINE: &'static str = "\n\n";
+use crate::error::{Error, Result};
+use crate::input;
+use crate::solution::{Answer, Solution};
 
 const NUMBER_OF_QUESTIONS: usize = 26;
 const ALPHABET: [char; 26] = [
@@ -17,21 +12,37 @@ const ALPHABET: [char; 26] = [
     'z',
 ];
 
-struct AnswerGroup {
+#[derive(Debug)]
+pub struct AnswerGroup {
     // an AnswerGroup is a vector of bool arrays. Each element in the vector
     //   represents one person's answers. Each element in the array of answers
     //   represents either the yes (true) or no (false) answer.
-    personal_answers: Vec<[bool; NUMBER_OF_QUESTIONS]>,
+    pub personal_answers: Vec<[bool; NUMBER_OF_QUESTIONS]>,
 }
 
 impl AnswerGroup {
+    // get_any_affirmative_answers returns the total number of questions
+    //  that anyone in this group answered yes to.
+    pub fn get_any_affirmative_answers(&self) -> u32 {
+        let mut any_answers: [bool; NUMBER_OF_QUESTIONS] = [false; NUMBER_OF_QUESTIONS];
+        for person in self.personal_answers.iter() {
+            for (ix, answer) in person.iter().enumerate() {
+                if *answer {
+                    any_answers[ix] = true;
+                }
+            }
+        }
+
+        any_answers.iter().filter(|ans| **ans).count() as u32
+    }
+
     // get_combined_affirmative_answers returns the total number of questions
     //  that everyone in this group answered yes to.
     pub fn get_combined_affirmative_answers(&self) -> u32 {
         let mut combined_answers: [bool; NUMBER_OF_QUESTIONS] = [true; NUMBER_OF_QUESTIONS];
         for person in self.personal_answers.iter() {
             for (ix, answer) in person.iter().enumerate() {
-                if *answer == false {
+                if !*answer {
                     combined_answers[ix] = false;
                 }
             }
@@ -39,32 +50,37 @@ impl AnswerGroup {
 
         let mut combined_yes_count = 0;
         for ans in combined_answers.iter() {
-            if *ans == true {
+            if *ans {
                 combined_yes_count += 1;
             }
         }
         
-        return combined_yes_count;
+        combined_yes_count
     }
 }
 
-// Construct and return a vec of BoardingPass structs from the input string
-fn build_answer_groups(input: &str) -> Vec<AnswerGroup> {
+// Construct and return a vec of AnswerGroup structs from the input string
+pub fn build_answer_groups(input: &str) -> Result<Vec<AnswerGroup>> {
     let mut groups: Vec<AnswerGroup> = Vec::new();
     
-    for input_group in input.split(BLANK_LINE) {
+    // each group is separated by a blank line
+    for input_group in input::records(input) {
         // initialize BoardingPass as empty
         let mut group = AnswerGroup { 
             personal_answers: Vec::new(),
         };
 
         // each person in the group is represented by a new line
-        for person in input_group.lines() {
+        for person in input_group.lines.iter() {
             let mut input_person: [bool; NUMBER_OF_QUESTIONS] = [ false; NUMBER_OF_QUESTIONS ];
             // each character in the line represents a yes answer
-            for letter in person.chars() {
-                if let Some(answer_ix) = ALPHABET.iter().position(|&c| c == letter) {
-                    input_person[answer_ix] = true;
+            for (ix, letter) in person.text.char_indices() {
+                match ALPHABET.iter().position(|&c| c == letter) {
+                    Some(answer_ix) => input_person[answer_ix] = true,
+                    None => {
+                        return Err(Error::parse(format!("unexpected question {:?}", letter))
+                            .at(person.number, ix + 1));
+                    }
                 }
             }
             group.personal_answers.push(input_person);
@@ -72,20 +88,64 @@ fn build_answer_groups(input: &str) -> Vec<AnswerGroup> {
         groups.push(group);
     }
 
-    return groups;
+    Ok(groups)
 }
 
-pub fn run(input: &str) {
-    // todo: error handling for missing file
-    let mut file = File::open(input).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-
-    let answer_groups = build_answer_groups(&contents);
-    let group_answer_totals: Vec<u32> = answer_groups
-        .iter()
-        .map(|grp| grp.get_combined_affirmative_answers())
-        .collect();
-    let sum: u32 = group_answer_totals.iter().sum();
-    println!("Sum of yes answers: {}", sum);
-}
\ No newline at end of file
+pub struct CustomCustoms;
+
+impl Solution for CustomCustoms {
+    type Input = Vec<AnswerGroup>;
+
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Custom Customs";
+
+    fn parse(&self, input: &str) -> Result<Vec<AnswerGroup>> {
+        build_answer_groups(input)
+    }
+
+    // part 1: sum of the questions that anyone in each group answered yes to
+    fn part1(&self, answer_groups: &Vec<AnswerGroup>) -> Result<Answer> {
+        let sum: u32 = answer_groups
+            .iter()
+            .map(|grp| grp.get_any_affirmative_answers())
+            .sum();
+        Ok(Answer::from(sum))
+    }
+
+    // part 2: sum of the questions that everyone in each group answered yes to
+
+    fn part2(&self, answer_groups: &Vec<AnswerGroup>) -> Result<Answer> {
+        let group_answer_totals: Vec<u32> = answer_groups
+            .iter()
+            .map(|grp| grp.get_combined_affirmative_answers())
+            .collect();
+        let sum: u32 = group_answer_totals.iter().sum();
+        Ok(Answer::from(sum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_groups() {
+        let groups = build_answer_groups(include_str!("../examples/day06/example.txt")).unwrap();
+        let any: Vec<u32> = groups.iter().map(|g| g.get_any_affirmative_answers()).collect();
+        let all: Vec<u32> = groups.iter().map(|g| g.get_combined_affirmative_answers()).collect();
+        assert_eq!(any, vec![3, 3, 3, 1, 1]);
+        assert_eq!(all, vec![3, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn extra_blank_lines_do_not_make_empty_groups() {
+        let groups = build_answer_groups("\nabc\r\n\r\n\r\nab\r\n\r\n").unwrap();
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn unexpected_question_is_located() {
+        let err = build_answer_groups("abc\n\nab\naB\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(4), Some(2)));
+    }
+}
@@ -0,0 +1,145 @@
+/******************************************************************************/
+/* Error: the error type returned by every parser and solver                  */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+use std::any::Any;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// ErrorKind: what went wrong, broadly. The runner uses this to pick an exit
+//   code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // the input couldn't be read
+    Io,
+    // the input was read, but isn't valid puzzle input
+    Parse,
+    // the input is valid, but the puzzle has no answer for it
+    NoSolution,
+    // the answer was found, but is too big for the type it's computed in
+    Overflow,
+    // the input is valid, but too big or unusual for the method asked for
+    Unsupported,
+    // the solver panicked; this is always a bug
+    Panic,
+}
+
+// Error: an ErrorKind plus as much context as we know about where it happened.
+// line and column are 1-based, to match what an editor shows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub path: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub description: String,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new(kind: ErrorKind, description: impl Into<String>) -> Self {
+        Error {
+            kind,
+            path: None,
+            line: None,
+            column: None,
+            description: description.into(),
+        }
+    }
+
+    // Build an error for a failed read of the file at path
+    pub fn io(path: impl AsRef<Path>, err: io::Error) -> Self {
+        Error::new(ErrorKind::Io, err.to_string()).in_file(path)
+    }
+
+    pub fn parse(description: impl Into<String>) -> Self {
+        Error::new(ErrorKind::Parse, description)
+    }
+
+    pub fn no_solution(description: impl Into<String>) -> Self {
+        Error::new(ErrorKind::NoSolution, description)
+    }
+
+    pub fn panic(description: impl Into<String>) -> Self {
+        Error::new(ErrorKind::Panic, description)
+    }
+
+    // Build an error from a caught panic's payload. panic!() payloads are
+    //   almost always a &str or a String; anything else gets a generic
+    //   message.
+    pub fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "panicked".to_string()
+        };
+        Error::panic(format!("panicked: {}", message))
+    }
+
+    // Set the line and column of the input where the error happened
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    // Set the file the input came from. Parsers only see the input's text, so
+    //   whoever read the file is responsible for adding its path.
+    pub fn in_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    // The process exit code the runner should use for this error
+    pub fn exit_code(&self) -> i32 {
+        match self.kind {
+            ErrorKind::Io          => 2,
+            ErrorKind::Parse       => 3,
+            ErrorKind::NoSolution  => 4,
+            ErrorKind::Overflow    => 5,
+            ErrorKind::Unsupported => 6,
+            // the same code as an uncaught panic
+            ErrorKind::Panic       => 101,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    // format as "path:line:column: description", leaving out whatever
+    //   context we don't have
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+            if let Some(column) = self.column {
+                write!(f, "{}:", column)?;
+            }
+        }
+        if self.path.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.description)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Get the 1-based column of token within line. token must be a slice of line,
+//   which is what str::split and friends hand back.
+pub fn column_of(line: &str, token: &str) -> usize {
+    (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize) + 1
+}
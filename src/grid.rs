@@ -0,0 +1,249 @@
+/******************************************************************************/
+/* Grid: a rectangular 2D map of cells, e.g. the toboggan map                 */
+/*   Cells are stored row by row, and addressed by (x, y) with (0, 0) at the  */
+/*   top left: x counts columns to the right, y counts rows down.             */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+use crate::error::{Error, Result};
+use crate::input;
+use std::fmt;
+use std::iter::StepBy;
+use std::slice::{self, Chunks};
+
+/******************************************************************************/
+/* Constant definitions                                                       */
+/******************************************************************************/
+// NEIGHBOUR_OFFSETS: the (x, y) offsets of the 8 cells around a cell, in
+//   reading order
+const NEIGHBOUR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1,  0),          (1,  0),
+    (-1,  1), (0,  1), (1,  1),
+];
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// Cell: something that can be read from, and written as, a single char of a
+//   grid
+pub trait Cell: Sized {
+    // the chars a cell can be written as, for error messages, e.g. "'#' or '.'"
+    const EXPECTED: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+// Grid: width * height cells. Never empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Build a grid from its rows, which must all be the same width.
+    // rows are numbered from 1 in errors, as input lines are.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = match rows.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(Error::parse("empty grid")),
+        };
+        let height = rows.len();
+
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for (row_ix, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::parse(format!(
+                    "row is {} cells wide; expected {}, like the first row",
+                    row.len(),
+                    width
+                ))
+                .at(row_ix + 1, row.len().min(width) + 1));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid { width, height, cells })
+    }
+
+    // Build a grid from its rows, padding any row narrower than the widest
+    //   one with fill
+    pub fn from_rows_padded(mut rows: Vec<Vec<T>>, fill: T) -> Result<Self>
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in rows.iter_mut() {
+            row.resize(width, fill.clone());
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // The cell at (x, y), or None if that's off the grid
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // The cell at (x, y), with the grid repeating forever in every direction
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    // The cells next to (x, y), including diagonally, as (x, y, cell).
+    // Only cells on the grid are included, so an edge cell has fewer than 8.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize, &T)> {
+        NEIGHBOUR_OFFSETS.iter().filter_map(move |&(dx, dy)| {
+            let nx = (x as isize).checked_add(dx)?;
+            let ny = (y as isize).checked_add(dy)?;
+            if nx < 0 || ny < 0 {
+                return None;
+            }
+            let (nx, ny) = (nx as usize, ny as usize);
+            self.get(nx, ny).map(|cell| (nx, ny, cell))
+        })
+    }
+
+    // Row y, from left to right
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    // Column x, from top to bottom
+    pub fn column(&self, x: usize) -> Option<StepBy<slice::Iter<'_, T>>> {
+        if x < self.width {
+            Some(self.cells[x..].iter().step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    // Every row, from top to bottom
+    pub fn rows(&self) -> Chunks<'_, T> {
+        self.cells.chunks(self.width)
+    }
+
+    // Every cell in reading order, as (x, y, cell)
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(ix, cell)| (ix % width, ix / width, cell))
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    // Parse a grid with one row per line and one cell per char
+    pub fn parse(input: &str) -> Result<Self> {
+        Grid::from_rows(input::grid(input, T::EXPECTED, T::from_char)?)
+    }
+
+    // Parse a grid as parse does, but pad short rows with fill rather than
+    //   rejecting them
+    pub fn parse_padded(input: &str, fill: T) -> Result<Self>
+    where
+        T: Clone,
+    {
+        Grid::from_rows_padded(input::grid(input, T::EXPECTED, T::from_char)?, fill)
+    }
+}
+
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Cell for u8 {
+        const EXPECTED: &'static str = "a digit";
+
+        fn from_char(c: char) -> Option<Self> {
+            c.to_digit(10).map(|digit| digit as u8)
+        }
+
+        fn to_char(&self) -> char {
+            (b'0' + self) as char
+        }
+    }
+
+    fn digits() -> Grid<u8> {
+        Grid::parse("123\n456\n").unwrap()
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn ragged_and_bad_cells_are_located() {
+        let err = Grid::<u8>::parse("123\n45\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+        assert_eq!(Grid::parse_padded("123\n45\n", 0).unwrap().to_string(), "123\n450\n");
+        let err = Grid::<u8>::parse("123\n4x6\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn accessors() {
+        let mut grid = digits();
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(*grid.get_wrapping(3, 0), 1);
+        assert_eq!(*grid.get_wrapping(-1, -1), 6);
+        *grid.get_mut(0, 0).unwrap() = 9;
+        assert_eq!(grid.row(0), Some(&[9, 2, 3][..]));
+        assert_eq!(grid.column(1).unwrap().copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert!(grid.column(3).is_none());
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = digits();
+        let around = |x, y| grid.neighbours(x, y).map(|(_, _, &cell)| cell).collect::<Vec<_>>();
+        assert_eq!(around(0, 0), vec![2, 4, 5]);
+        assert_eq!(around(1, 1), vec![1, 2, 3, 4, 6]);
+    }
+}
@@ -1,5 +1,5 @@
 /******************************************************************************/
-/* Advent of Code 2020 day 7:                                                 */
+/* Advent of Code 2020 day 8:                                                 */
 /*   -- Handheld Halting --                                                   */
 /******************************************************************************/
 /******************************************************************************/
@@ -10,16 +10,17 @@ extern crate nom;
 use nom::{
     alt,
     char,
-    character::is_digit,
+    character::complete::digit1,
     map_res,
     recognize,
     tag,
-    take_while,
     named
 };
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::input;
+use crate::solution::{Answer, Solution};
+use std::convert::TryFrom;
 
 /******************************************************************************/
 /* Constant definitions                                                       */
@@ -29,19 +30,29 @@ use std::io::{BufRead, BufReader};
 /* Structure/enum definitions                                                 */
 /******************************************************************************/
 #[derive(Clone, Copy, Debug)]
-enum Operation {
+pub enum Operation {
     Acc,
     Jmp,
     Nop,
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Instruction {
-    opcode: Operation,
-    argument: usize,
-    // if arg_positive is true, the argument is meant to be treated as a positive number.
-    //   else, it should be considered negative.
-    arg_positive: bool,
+pub struct Instruction {
+    pub opcode: Operation,
+    // the signed argument; it has to fit in the 32-bit accumulator
+    pub argument: i32,
+}
+
+// How the machine stopped running. Either way, carries the value of the
+//   accumulator when it stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MachineExit {
+    // the instruction pointer ran off the end of the program
+    Halted(i32),
+    // an instruction was about to run a second time
+    Looped(i32),
+    // a Jmp sent the instruction pointer before the start of the program
+    Crashed(i32),
 }
 
 /******************************************************************************/
@@ -57,12 +68,13 @@ named!(parse_instr<&[u8], &str>,
     )
 );
 
-named!(parse_number<&[u8], usize>,
+// the digits of a number, which are checked for size once the sign is known
+named!(parse_number<&[u8], &str>,
     map_res!(
-        recognize!(
-            take_while!( is_digit ) 
-        ),
-        |bytes: &[u8]| std::str::from_utf8(bytes).unwrap().parse::<usize>()
+        // use the "complete" digit parser, since lines have no trailing
+        //   newline to terminate the number
+        digit1,
+        std::str::from_utf8
     )
 );
 
@@ -74,58 +86,59 @@ named!(parse_single_space<&[u8], char>, char!( ' ' ) );
 /* Subroutines                                                                */
 /******************************************************************************/
 // convert an input line to an instruction
-fn line_to_inst(input: &str) -> Instruction {
+// line_no is the 1-based line number of the input line, used for errors
+pub fn line_to_inst(line_no: usize, line: &str) -> Result<Instruction> {
+    // build an error pointing at the start of the unparsed input
+    let fail = |rest: &[u8], expected: &str| {
+        Error::parse(format!("expected {}", expected))
+            .at(line_no, line.len() - rest.len() + 1)
+    };
+
     // parse one line.
     // first, get the operation
-    let input = input.as_bytes();
-    let (input, opcode) = parse_instr(input).unwrap();
+    let input = line.as_bytes();
+    let (input, opcode) = parse_instr(input)
+        .map_err(|_| fail(input, "an operation (acc, jmp or nop)"))?;
     // next, skip whitespace
-    let (input, _) = parse_single_space(input).unwrap();
+    let (input, _) = parse_single_space(input)
+        .map_err(|_| fail(input, "a space"))?;
     // get the sign...
-    let (input, sign) = parse_sign(input).unwrap();
+    let (input, sign) = parse_sign(input)
+        .map_err(|_| fail(input, "a sign (+ or -)"))?;
     // and finally the numerical argument
-    let (_, num) = parse_number(input).unwrap();
+    let digits_start = input;
+    let (input, digits) = parse_number(input)
+        .map_err(|_| fail(input, "a number"))?;
+    if !input.is_empty() {
+        return Err(fail(input, "the end of the line"));
+    }
+    let argument = format!("{}{}", sign, digits).parse::<i32>().map_err(|_| {
+        Error::parse(format!("argument {}{} doesn't fit in 32 bits", sign, digits))
+            .at(line_no, line.len() - digits_start.len() + 1)
+    })?;
 
-    Instruction {
+    Ok(Instruction {
         opcode: match opcode {
             "acc" => Operation::Acc,
             "jmp" => Operation::Jmp,
-            "nop" => Operation::Nop,
-            _     => panic!("Invalid opcode {}", opcode)
+            // parse_instr only accepts the three opcodes
+            _     => Operation::Nop,
         },
 
-        argument: num,
-        
-        arg_positive: match sign {
-            '-' => false,
-            '+' => true,
-            _   => panic!("Invalid sign!")
-        }
-    }
+        argument,
+    })
 }
 
 
-fn parse_input(input: &str) -> Result<Vec<Instruction>, Box<dyn Error>> {
-    let file = File::open(input)?;
-    let mut reader = BufReader::new(file);
-
-    let mut line = String::new();
-    let mut instructions: Vec<Instruction> = Vec::new();
-    while let Ok(line_length) = reader.read_line(&mut line) {
-        match line_length {
-            // if line_length is 0, we've reached end of file
-            0 => break,
-            _ => {
-                instructions.push(line_to_inst(&line));
-                line.clear();
-            }
-        }
-    }
-
-    Ok(instructions)
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>> {
+    input::lines(input)
+        .map(|line| line_to_inst(line.number, line.text))
+        .collect()
 }
 
-pub fn run_machine(input: Vec<Instruction>) -> Option<i32> {
+// run the program until it halts, loops or crashes.
+// fails if the accumulator or the instruction pointer overflows.
+pub fn run_machine(input: &[Instruction]) -> Result<MachineExit> {
     // pair each instruction with a bool representing if the instruction has been
     //   visited or not.
     let mut visited_instrs: Vec<(Instruction, bool)> = Vec::new();
@@ -134,28 +147,32 @@ pub fn run_machine(input: Vec<Instruction>) -> Option<i32> {
     }
 
     let mut accumulator: i32 = 0;
-    let mut instruction_index = 0;
-    
+    let mut instruction_index: usize = 0;
+
     // execute instructions
     while instruction_index < visited_instrs.len() {
-        let mut instr = visited_instrs.get_mut(instruction_index).unwrap();
+        let instr = visited_instrs.get_mut(instruction_index).unwrap();
         match instr.1 {
-            true => return None,
+            true => return Ok(MachineExit::Looped(accumulator)),
             false => {
                 match instr.0.opcode {
                     Operation::Acc => {
-                        if instr.0.arg_positive {
-                            accumulator += instr.0.argument as i32;
-                        } else {
-                            accumulator -= instr.0.argument as i32;
-                        }
+                        accumulator = accumulator.checked_add(instr.0.argument).ok_or_else(|| {
+                            Error::new(ErrorKind::Overflow, "the accumulator overflowed")
+                        })?;
                         instruction_index += 1;
                     },
                     Operation::Jmp => {
-                        if instr.0.arg_positive {
-                            instruction_index += instr.0.argument;
-                        } else {
-                            instruction_index -= instr.0.argument;
+                        // a negative index means we jumped before the start
+                        let new_index = isize::try_from(instruction_index)
+                            .ok()
+                            .and_then(|index| index.checked_add(instr.0.argument as isize))
+                            .ok_or_else(|| {
+                                Error::new(ErrorKind::Overflow, "the instruction pointer overflowed")
+                            })?;
+                        match usize::try_from(new_index) {
+                            Ok(new_index) => instruction_index = new_index,
+                            Err(_) => return Ok(MachineExit::Crashed(accumulator)),
                         }
                     },
                     Operation::Nop => {
@@ -170,57 +187,94 @@ pub fn run_machine(input: Vec<Instruction>) -> Option<i32> {
     // if we made it here, there was no loop in the machine;
     //   the machine halted successfully.
     // return the accumulator value
-    return Some(accumulator);
+    Ok(MachineExit::Halted(accumulator))
 }
 
 /******************************************************************************/
-/* Main routine                                                               */
+/* Solution                                                                   */
 /******************************************************************************/
-pub fn run(input: &str) {
-    // parse input file and build a vec of instructions
-    let instructions: Vec<Instruction> = parse_input(input).unwrap();
-
-    loop {
-        // create a mutable copy of the instructions so we can modify one
-        let mut modified_instructions = instructions.clone();
-        for instr in modified_instructions {
-            match instr.opcode {
+pub struct HandheldHalting;
+
+impl Solution for HandheldHalting {
+    type Input = Vec<Instruction>;
+
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Handheld Halting";
+
+    // parse input and build a vec of instructions
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>> {
+        parse_input(input)
+    }
+
+    // part 1: the value of the accumulator right before any instruction
+    //   would run a second time
+    fn part1(&self, instructions: &Vec<Instruction>) -> Result<Answer> {
+        match run_machine(instructions)? {
+            MachineExit::Looped(accumulator) => Ok(Answer::from(accumulator)),
+            MachineExit::Halted(_) => Err(Error::no_solution("the program halted without looping")),
+            MachineExit::Crashed(_) => Err(Error::no_solution("the program jumped before its start")),
+        }
+    }
+
+    // part 2: exactly one Jmp or Nop is corrupted. Find it, and return the
+    //   value of the accumulator once the fixed program halts.
+    fn part2(&self, instructions: &Vec<Instruction>) -> Result<Answer> {
+        for ix in 0..instructions.len() {
+            // create a mutable copy of the instructions so we can modify one
+            let mut modified_instructions = instructions.clone();
+            let instr = &mut modified_instructions[ix];
+            instr.opcode = match instr.opcode {
                 // if the operation is an Acc, continue... all Accs are correct instructions
                 Operation::Acc => continue,
-    
                 // if it's a Jmp, convert it to a Nop and run the machine
-                Operation::Jmp => {
-                    instr.opcode = Operation::Nop;
-                    let accumulator = run_machine(instructions);
-                    match accumulator {
-                        Some(result) => {
-                            println!("Found result! {}", result);
-                            break;
-                        },
-                        None => {
-                            // turn instruction back into a Jmp
-                            instr.opcode = Operation::Jmp;
-                        }
-                    }
-                },
-                
+                Operation::Jmp => Operation::Nop,
                 // if it's a Nop, convert it to a Jmp and run the machine
-                Operation::Nop => {
-                    instr.opcode = Operation::Jmp;
-                    let accumulator = run_machine(instructions);
-                    match accumulator {
-                        Some(result) => {
-                            println!("Found result! {}", result);
-                            break;
-                        },
-                        None => {
-                            // turn instruction back into a Nop
-                            instr.opcode = Operation::Nop;
-                        }
-                    }
-                }
+                Operation::Nop => Operation::Jmp,
+            };
+
+            if let MachineExit::Halted(result) = run_machine(&modified_instructions)? {
+                return Ok(Answer::from(result));
             }
         }
+
+        Err(Error::no_solution("no single Jmp/Nop swap makes the program halt"))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day08/example.txt");
 
-}
\ No newline at end of file
+    #[test]
+    fn example_loops_with_accumulator_5() {
+        let instructions = parse_input(EXAMPLE).unwrap();
+        assert_eq!(run_machine(&instructions).unwrap(), MachineExit::Looped(5));
+    }
+
+    #[test]
+    fn jump_before_start_crashes() {
+        let instructions = parse_input("acc +2\njmp -2\n").unwrap();
+        assert_eq!(run_machine(&instructions).unwrap(), MachineExit::Crashed(2));
+    }
+
+    #[test]
+    fn accumulator_overflow_is_an_error() {
+        let instructions = parse_input("acc +2147483647\nacc +1\n").unwrap();
+        assert_eq!(run_machine(&instructions).unwrap_err().kind, ErrorKind::Overflow);
+    }
+
+    #[test]
+    fn huge_arguments_are_located() {
+        let err = parse_input("nop +0\njmp +18446744073709551615\n").unwrap_err();
+        assert_eq!((err.kind, err.line, err.column), (ErrorKind::Parse, Some(2), Some(6)));
+        assert_eq!(line_to_inst(1, "acc -2147483648").unwrap().argument, i32::MIN);
+    }
+
+    #[test]
+    fn bad_sign_is_located() {
+        let err = line_to_inst(3, "jmp *4").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(3), Some(5)));
+    }
+}
@@ -1,5 +1,5 @@
 /******************************************************************************/
-/* Advent of Code 2020 day 6:                                                 */
+/* Advent of Code 2020 day 7:                                                 */
 /*   -- Handy Haversacks --                                                   */
 /******************************************************************************/
 /******************************************************************************/
@@ -8,15 +8,16 @@
 extern crate petgraph;
 extern crate regex;
 
+use crate::error::{column_of, Error, ErrorKind, Result};
+use crate::input;
+use crate::solution::{Answer, Solution};
 use core::cmp::Ordering;
 use petgraph::Graph;
 use petgraph::algo::has_path_connecting;
 use petgraph::graph::NodeIndex;
 use regex::Regex;
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 
 /******************************************************************************/
 /* Constant definitions                                                       */
@@ -31,16 +32,16 @@ const MY_BAG_COLOR: &str = "shiny gold";
 // BagChild: A "shallow copy" of a "child color" bag which is contained by
 //   a bag of "parent color".
 #[derive(Clone, Debug)]
-struct BagChild {
-    color: String,      // color of the child bag
-    count: u32,         // number of child-color bags contained in the parent bag
+pub struct BagChild {
+    pub color: String,      // color of the child bag
+    pub count: u32,         // number of child-color bags contained in the parent bag
 }
 
 // "Parent bag" which contains a number of other bags.
 #[derive(Debug)]
-struct Bag {
-    color: String,
-    contents: Vec<BagChild>,
+pub struct Bag {
+    pub color: String,
+    pub contents: Vec<BagChild>,
 }
 
 // Ord implementation for sorting
@@ -68,61 +69,66 @@ impl Eq for Bag { }
 /******************************************************************************/
 /* Subroutines                                                                */
 /******************************************************************************/
-// Parse the input file and build a set of "shallow copies" of bags. These bags
+// Parse the input and build a set of "shallow copies" of bags. These bags
 //   are only one level deep.
-fn parse_input(input: &str) -> Result<Vec<Bag>, Box<dyn Error>> {
-    let file = File::open(input)?;
-    
-    let contained_bag_regex = Regex::new(r"\sbag(s?)(,|\.)(\s?)")?;
+pub fn parse_input(input: &str) -> Result<Vec<Bag>> {
+    let contained_bag_regex = Regex::new(r"\sbag(s?)(,|\.)(\s?)").unwrap();
 
     // construct data structure from lines
     let mut bags: Vec<Bag> = Vec::new();
-    let mut reader = BufReader::new(file);
 
-    let mut line = String::new();
     let mut contained_bags: Vec<BagChild> = Vec::new();
-    while let Ok(line_length) = reader.read_line(&mut line) {
-        match line_length {
-            // if line_length is 0, we've reached end of file
-            0 => break,
-            _ => {
-                let split_input_line: Vec<&str> = line.split(" bags contain ").collect();
-                
-                // first string in input line is color of the bag
-                let color = split_input_line.get(0).unwrap();
-
-                let contained_bag_list = split_input_line.get(1).unwrap();
-                for contained_bag_str in contained_bag_regex.split(contained_bag_list) {
-                    // it's possible that a bag contains no other bags
-                    // skip this bag if this is the case
-                    if !contained_bag_str.trim().is_empty() && !contained_bag_str.contains("no other") {
-                        // split contained_bag_str into number and color
-                        // split the string on the first space.
-                        // number comes first, then color
-                        let mut splitter = contained_bag_str.splitn(2, ' ');
-                            
-                        let contained_num = splitter.next().unwrap().parse()?;
-        
-                        let contained_color = splitter.next().unwrap();
-        
-                        contained_bags.push(BagChild {
-                            color: contained_color.to_string(),
-                            count: contained_num,
-                        });
+    for input_line in input::lines(input) {
+        let (line_no, line) = (input_line.number, input_line.text);
+        let split_input_line: Vec<&str> = line.split(" bags contain ").collect();
+        if split_input_line.len() != 2 {
+            return Err(Error::parse("expected \"<color> bags contain <contents>\"")
+                .at(line_no, 1));
+        }
+
+        // first string in input line is color of the bag
+        let color = split_input_line[0];
+
+        let contained_bag_list = split_input_line[1];
+        for contained_bag_str in contained_bag_regex.split(contained_bag_list) {
+            // it's possible that a bag contains no other bags
+            // skip this bag if this is the case
+            if !contained_bag_str.trim().is_empty() && !contained_bag_str.contains("no other") {
+                // split contained_bag_str into number and color
+                // split the string on the first space.
+                // number comes first, then color
+                let mut splitter = contained_bag_str.splitn(2, ' ');
+
+                // splitn always returns at least one item
+                let num_str = splitter.next().unwrap();
+                let contained_num = num_str.parse().map_err(|err| {
+                    Error::parse(format!("invalid bag count {:?}: {}", num_str, err))
+                        .at(line_no, column_of(line, num_str))
+                })?;
+
+                let contained_color = match splitter.next() {
+                    Some(contained_color) => contained_color,
+                    None => {
+                        return Err(Error::parse(format!("no color for {:?}", contained_bag_str))
+                            .at(line_no, column_of(line, contained_bag_str)));
                     }
-                }
+                };
 
-                bags.push(Bag { 
-                    color: color.to_string(),
-                    contents: contained_bags.clone(),
+                contained_bags.push(BagChild {
+                    color: contained_color.to_string(),
+                    count: contained_num,
                 });
-
-                contained_bags.clear();
-                line.clear();   
             }
         }
+
+        bags.push(Bag {
+            color: color.to_string(),
+            contents: contained_bags.clone(),
+        });
+
+        contained_bags.clear();
     }
-    
+
     Ok(bags)
 }
 
@@ -130,7 +136,7 @@ fn parse_input(input: &str) -> Result<Vec<Bag>, Box<dyn Error>> {
 /* build_bag_tree: build a tree of bags, where each bag has a number of       */
 /*   children                                                                 */
 /******************************************************************************/
-fn build_bag_tree(rules: &Vec<Bag>) -> (Graph<String, u32>, HashMap<String, NodeIndex>) {
+pub fn build_bag_tree(rules: &[Bag]) -> Result<(Graph<String, u32>, HashMap<String, NodeIndex>)> {
     let mut bag_graph: Graph<String, u32> = Graph::new();
     // construct a map of color names to NodeIndexes
     let mut bag_nodes: HashMap<String, NodeIndex> = HashMap::new();
@@ -143,62 +149,170 @@ fn build_bag_tree(rules: &Vec<Bag>) -> (Graph<String, u32>, HashMap<String, Node
     // build graph's edges
     for parent_bag in rules.iter() {
         for child_bag in parent_bag.contents.iter() {
+            // every parent has a node, since we just built them
             let parent_node = bag_nodes.get(&parent_bag.color).unwrap();
-            let child_node = bag_nodes.get(&child_bag.color).unwrap();
+            // ...but a child might not have a rule of its own
+            let child_node = bag_nodes.get(&child_bag.color).ok_or_else(|| {
+                Error::parse(format!(
+                    "{} bags contain {} bags, which have no rule",
+                    parent_bag.color, child_bag.color
+                ))
+            })?;
             
             // use child bag's count as the weight
             bag_graph.update_edge(*parent_node, *child_node, child_bag.count);
         }
     }
     
-    return (bag_graph, bag_nodes);
+    Ok((bag_graph, bag_nodes))
 }
 
-// recursively get the number of child (grandchild, etc) bags contained in the passed-in bag node
-fn get_child_bag_count(bag_graph: &Graph<String, u32>, my_bag_node: &NodeIndex) -> u32 {
-    println!("Recursing");
-    let mut count = 1;
+// get the number of bags in the passed-in bag node, counting itself and all of
+//   its child (grandchild, etc) bags
+// fails if a bag ends up inside itself, or if the count doesn't fit in a u64
+pub fn get_child_bag_count(bag_graph: &Graph<String, u32>, my_bag_node: &NodeIndex) -> Result<u64> {
+    let mut counted: HashMap<NodeIndex, u64> = HashMap::new();
+    let mut in_progress: HashSet<NodeIndex> = HashSet::new();
+    count_bags_recurse(bag_graph, *my_bag_node, &mut counted, &mut in_progress)
+}
 
-    let neighbors = bag_graph.neighbors(*my_bag_node);
-    for neighbor in neighbors {
-        let edge = bag_graph.find_edge(*my_bag_node, neighbor).unwrap();
-        count += bag_graph.edge_weight(edge).unwrap() * get_child_bag_count(bag_graph, &neighbor);
+// counted:     the bag counts already worked out, so that a bag reached by
+//              several paths is only counted once
+// in_progress: the bags whose counts are being worked out further up the
+//              recursion; reaching one of them again means the rules loop
+fn count_bags_recurse(
+    bag_graph: &Graph<String, u32>,
+    bag_node: NodeIndex,
+    counted: &mut HashMap<NodeIndex, u64>,
+    in_progress: &mut HashSet<NodeIndex>,
+) -> Result<u64> {
+    if let Some(count) = counted.get(&bag_node) {
+        return Ok(*count);
     }
-    
-    return count;
+    if !in_progress.insert(bag_node) {
+        return Err(Error::parse(format!(
+            "{} bags end up containing themselves",
+            bag_graph[bag_node]
+        )));
+    }
+
+    let overflow = || Error::new(ErrorKind::Overflow, "the number of bags overflowed");
+    let mut count: u64 = 1;
+    for neighbor in bag_graph.neighbors(bag_node) {
+        let edge = bag_graph.find_edge(bag_node, neighbor).unwrap();
+        let child_count = count_bags_recurse(bag_graph, neighbor, counted, in_progress)?;
+        count = u64::from(bag_graph[edge])
+            .checked_mul(child_count)
+            .and_then(|children| count.checked_add(children))
+            .ok_or_else(overflow)?;
+    }
+
+    in_progress.remove(&bag_node);
+    counted.insert(bag_node, count);
+    Ok(count)
 }
 
+// find the node for my bag in the tree
+fn find_my_bag(bag_nodes: &HashMap<String, NodeIndex>) -> Result<&NodeIndex> {
+    bag_nodes.get(MY_BAG_COLOR).ok_or_else(|| {
+        Error::no_solution(format!("no rule for {} bags", MY_BAG_COLOR))
+    })
+}
 
 /******************************************************************************/
-/* Main routine                                                               */
+/* Solution                                                                   */
 /******************************************************************************/
-pub fn run(input: &str) {
-    // parse input file and build a list of "rules" about bags
-    let bag_index: Vec<Bag> = parse_input(input).unwrap();
-
-    // build a tree of bags which contain other bags
-    // the weight (u32) value is the number of child bags that each bag contains
-    let bag_graph_rtn = build_bag_tree(&bag_index);
-    let bag_graph = bag_graph_rtn.0;
-    let bag_nodes = bag_graph_rtn.1;
-
-    let mut paths_to_goal = 0;
-    let my_bag_node = bag_nodes.get(MY_BAG_COLOR).unwrap();
-
-    for start_node in bag_nodes.values() {
-        // don't count the goal -> goal case as a path
-        if start_node == my_bag_node {
-            continue;
-        }
-        if has_path_connecting(&bag_graph, *start_node, *my_bag_node, None) {
-            paths_to_goal += 1;
+pub struct HandyHaversacks;
+
+impl Solution for HandyHaversacks {
+    type Input = Vec<Bag>;
+
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "Handy Haversacks";
+
+    // parse input and build a list of "rules" about bags
+    fn parse(&self, input: &str) -> Result<Vec<Bag>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, bag_index: &Vec<Bag>) -> Result<Answer> {
+        // build a tree of bags which contain other bags
+        // the weight (u32) value is the number of child bags that each bag contains
+        let (bag_graph, bag_nodes) = build_bag_tree(bag_index)?;
+
+        let mut paths_to_goal: u32 = 0;
+        let my_bag_node = find_my_bag(&bag_nodes)?;
+
+        for start_node in bag_nodes.values() {
+            // don't count the goal -> goal case as a path
+            if start_node == my_bag_node {
+                continue;
+            }
+            if has_path_connecting(&bag_graph, *start_node, *my_bag_node, None) {
+                paths_to_goal += 1;
+            }
         }
+
+        Ok(Answer::from(paths_to_goal))
+    }
+
+    fn part2(&self, bag_index: &Vec<Bag>) -> Result<Answer> {
+        let (bag_graph, bag_nodes) = build_bag_tree(bag_index)?;
+        let my_bag_node = find_my_bag(&bag_nodes)?;
+
+        // perform a depth-first search from my bag to get the total number of bags
+        //  that must be inside it
+        // have to subtract one from the result; don't count my own bag
+        let my_bag_contents = get_child_bag_count(&bag_graph, my_bag_node)? - 1;
+
+        Answer::try_from(my_bag_contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rule_with_contents() {
+        let bags = parse_input("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+        assert_eq!(bags[0].color, "light red");
+        let contents: Vec<(&str, u32)> =
+            bags[0].contents.iter().map(|child| (child.color.as_str(), child.count)).collect();
+        assert_eq!(contents, vec![("bright white", 1), ("muted yellow", 2)]);
     }
-    println!("Paths to goal (part 1 solution): {}", paths_to_goal);
 
-    // perform a depth-first search from my bag to get the total number of bags
-    //  that must be inside it
-    // have to subtract one from the result; don't count my own bag
-    let my_bag_contents = get_child_bag_count(&bag_graph, my_bag_node) - 1;
-    println!("Number of bags inside my bag (part 2): {}", my_bag_contents);
-}
\ No newline at end of file
+    #[test]
+    fn parse_rule_without_contents() {
+        let bags = parse_input("faded blue bags contain no other bags.").unwrap();
+        assert!(bags[0].contents.is_empty());
+    }
+
+    #[test]
+    fn cyclic_rules_are_an_error() {
+        let bags = parse_input(
+            "shiny gold bags contain 1 dark red bag.\ndark red bags contain 1 shiny gold bag.",
+        )
+        .unwrap();
+        let err = HandyHaversacks.part2(&bags).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Parse);
+    }
+
+    #[test]
+    fn huge_counts_overflow() {
+        // each level holds 4 billion of the next, so six levels overflow a u64
+        let rules: Vec<String> = (0..6)
+            .map(|level| format!("level{} bags contain 4000000000 level{} bags.", level, level + 1))
+            .chain(Some("level6 bags contain no other bags.".to_string()))
+            .collect();
+        let bags = parse_input(&rules.join("\n").replace("level0", "shiny gold")).unwrap();
+        let err = HandyHaversacks.part2(&bags).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Overflow);
+    }
+
+    #[test]
+    fn child_without_a_rule_is_an_error() {
+        let bags = parse_input("shiny gold bags contain 2 dark red bags.").unwrap();
+        assert!(build_bag_tree(&bags).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+/******************************************************************************/
+/* Image: a plain RGB raster, e.g. for drawing toboggan paths, and the file   */
+/*   formats it can be written as                                             */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+#[cfg(feature = "png")]
+extern crate png;
+
+use crate::error::{Error, ErrorKind, Result};
+use std::path::Path;
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+pub type Rgb = [u8; 3];
+
+// Image: width * height pixels, stored row by row from the top left
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    // binary PPM (P6), which needs no dependencies
+    Ppm,
+    // PNG, only available with the png feature
+    Png,
+}
+
+impl Image {
+    // A width * height image filled with background
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image { width, height, pixels: vec![background; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // Fill the size * size square whose top left corner is at
+    //   (x * size, y * size), clipped to the image
+    pub fn fill_block(&mut self, x: usize, y: usize, size: usize, colour: Rgb) {
+        let (height, width) = (self.height, self.width);
+        for py in (y * size..(y + 1) * size).take_while(|py| *py < height) {
+            for px in (x * size..(x + 1) * size).take_while(|px| *px < width) {
+                self.pixels[py * self.width + px] = colour;
+            }
+        }
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.reserve(self.pixels.len() * 3);
+        for pixel in self.pixels.iter() {
+            bytes.extend_from_slice(pixel);
+        }
+        bytes
+    }
+
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let encoding_error =
+            |err: png::EncodingError| Error::new(ErrorKind::Io, format!("couldn't encode PNG: {}", err));
+
+        let mut bytes: Vec<u8> = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+            encoder.set_color(png::ColorType::RGB);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().map_err(encoding_error)?;
+            let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+            writer.write_image_data(&data).map_err(encoding_error)?;
+        }
+        Ok(bytes)
+    }
+
+    #[cfg(not(feature = "png"))]
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        Err(png_unsupported())
+    }
+
+    // Encode the image in format
+    pub fn encode(&self, format: ImageFormat) -> Result<Vec<u8>> {
+        match format {
+            ImageFormat::Ppm => Ok(self.to_ppm()),
+            ImageFormat::Png => self.to_png(),
+        }
+    }
+}
+
+// the error for writing a PNG without the png feature
+fn png_unsupported() -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "PNG export needs the png feature; build with --features png, or write a .ppm",
+    )
+}
+
+impl ImageFormat {
+    // Pick the format from a file's extension
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("ppm") => Ok(ImageFormat::Ppm),
+            Some("png") => Ok(ImageFormat::Png),
+            _ => Err(Error::new(
+                ErrorKind::Unsupported,
+                format!("can't tell the image format of {}; use .ppm or .png", path.display()),
+            )),
+        }
+    }
+
+    // Fail if this build can't encode the format, so that a caller can find
+    //   out before doing the work of drawing an image
+    pub fn check_supported(self) -> Result<()> {
+        match self {
+            ImageFormat::Png if !cfg!(feature = "png") => Err(png_unsupported()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_are_clipped() {
+        let mut image = Image::new(3, 3, [0, 0, 0]);
+        image.fill_block(1, 1, 2, [255, 0, 0]);
+        assert_eq!(image.get(2, 2), Some([255, 0, 0]));
+        assert_eq!(image.get(1, 1), Some([0, 0, 0]));
+        assert_eq!(image.get(3, 3), None);
+    }
+
+    #[test]
+    fn ppm_has_header_then_pixels() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.fill_block(1, 0, 1, [1, 2, 3]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\x01\x02\x03".to_vec());
+        assert_eq!(ImageFormat::from_path(Path::new("map.PNG")).unwrap(), ImageFormat::Png);
+        assert!(ImageFormat::from_path(Path::new("map.gif")).is_err());
+    }
+
+    #[test]
+    fn png_support_matches_the_feature() {
+        assert!(ImageFormat::Ppm.check_supported().is_ok());
+        assert_eq!(ImageFormat::Png.check_supported().is_ok(), cfg!(feature = "png"));
+    }
+}
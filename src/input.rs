@@ -0,0 +1,278 @@
+/******************************************************************************/
+/* Input: where a puzzle's input comes from, and how to read it               */
+/*   The readers here normalise line endings and trailing whitespace, so the  */
+/*   days don't need to care whether an input was saved on Windows.           */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+use crate::error::{Error, Result};
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/******************************************************************************/
+/* Constant definitions                                                       */
+/******************************************************************************/
+// DEFAULT_INPUTS_DIR: where each day's input lives when no input is given,
+//   as <day>.txt
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+// STDIN_ARG: the input argument that means "read from stdin"
+pub const STDIN_ARG: &str = "-";
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    // input given directly, e.g. on the command line
+    Text(String),
+}
+
+// Line: one line of input, with trailing whitespace (including the "\r" of a
+//   CRLF line ending) removed.
+// number is 1-based, to match what an editor shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+// Record: a run of non-blank lines, separated from other records by one or
+//   more blank lines. Never empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub lines: Vec<Line<'a>>,
+}
+
+impl Line<'_> {
+    pub fn is_blank(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+impl<'a> Record<'a> {
+    // the line number of the record's first line
+    pub fn first_line(&self) -> usize {
+        self.lines[0].number
+    }
+}
+
+impl Source {
+    // The default input for a day: <inputs_dir>/<day>.txt
+    pub fn for_day(inputs_dir: &Path, day: u32) -> Self {
+        Source::File(inputs_dir.join(format!("{}.txt", day)))
+    }
+
+    // Pick the source from the command line arguments.
+    // text: input given inline, which takes priority over everything else
+    // arg:  an input file path, or "-" for stdin
+    // If neither is given, fall back to the day's file in DEFAULT_INPUTS_DIR.
+    pub fn from_args(arg: Option<&str>, text: Option<&str>, day: u32) -> Self {
+        match (text, arg) {
+            (Some(text), _)         => Source::Text(text.to_string()),
+            (None, Some(STDIN_ARG)) => Source::Stdin,
+            (None, Some(path))      => Source::File(PathBuf::from(path)),
+            (None, None)            => Source::for_day(Path::new(DEFAULT_INPUTS_DIR), day),
+        }
+    }
+
+    // Read the whole input
+    pub fn load(&self) -> Result<String> {
+        match self {
+            Source::File(path) => fs::read_to_string(path).map_err(|err| Error::io(path, err)),
+            Source::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|err| Error::io(self.to_string(), err))?;
+                Ok(contents)
+            }
+            Source::Text(text) => Ok(text.clone()),
+        }
+    }
+
+    // Open the input to be read a line at a time, rather than all at once
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => {
+                let file = fs::File::open(path).map_err(|err| Error::io(path, err))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Source::Stdin => Ok(Box::new(BufReader::new(io::stdin()))),
+            Source::Text(text) => Ok(Box::new(io::Cursor::new(text.clone().into_bytes()))),
+        }
+    }
+
+    // Attach this source to an error, so it's reported as coming from here
+    pub fn locate(&self, err: Error) -> Error {
+        err.in_file(self.to_string())
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin      => write!(f, "<stdin>"),
+            Source::Text(_)    => write!(f, "<text>"),
+        }
+    }
+}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Split input into lines. Blank lines at the end of the input are dropped;
+//   blank lines anywhere else are kept, so that line numbers stay accurate.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    // str::lines already strips "\r\n", but not a stray "\r" or trailing
+    //   spaces, so trim every line as well
+    input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(|(line_ix, text)| Line { number: line_ix + 1, text: text.trim_end() })
+}
+
+// Split input into records separated by blank lines, e.g. passports or
+//   groups of customs answers. Any number of blank lines separates two
+//   records, and blank lines at the start or end are ignored.
+pub fn records(input: &str) -> Vec<Record<'_>> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut current: Vec<Line> = Vec::new();
+
+    for line in lines(input) {
+        if line.is_blank() {
+            if !current.is_empty() {
+                records.push(Record { lines: current });
+                current = Vec::new();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        records.push(Record { lines: current });
+    }
+
+    records
+}
+
+// Parse input with one integer per line, e.g. an expense report
+pub fn integers<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    lines(input)
+        .map(|line| {
+            line.text.parse::<T>().map_err(|err| {
+                Error::parse(format!("invalid integer {:?}: {}", line.text, err))
+                    .at(line.number, 1)
+            })
+        })
+        .collect()
+}
+
+// Parse input with one integer per line as it's read, as integers does, so
+//   that the input never has to be held in memory all at once.
+// Errors reading from reader are reported as coming from source.
+pub fn read_integers<T, R>(reader: R, source: &Source) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    R: BufRead,
+{
+    let mut numbers: Vec<T> = Vec::new();
+    // a blank line is only an error if something comes after it, so
+    //   remember the first one until we know
+    let mut blank_line_no: Option<usize> = None;
+
+    for (line_ix, line) in reader.lines().enumerate() {
+        let line = line.map_err(|err| Error::io(source.to_string(), err))?;
+        let text = line.trim_end();
+        if text.is_empty() {
+            blank_line_no = blank_line_no.or(Some(line_ix + 1));
+            continue;
+        }
+        if let Some(line_no) = blank_line_no {
+            return Err(Error::parse("invalid integer \"\": blank line").at(line_no, 1));
+        }
+
+        let number = text.parse::<T>().map_err(|err| {
+            Error::parse(format!("invalid integer {:?}: {}", text, err)).at(line_ix + 1, 1)
+        })?;
+        numbers.push(number);
+    }
+
+    Ok(numbers)
+}
+
+// Parse input as a grid of cells, one row per line and one cell per char.
+// cell converts a char to a cell, returning None if the char isn't valid;
+//   expected describes the valid chars for the error message.
+// Rows may not be empty.
+pub fn grid<T, F>(input: &str, expected: &str, cell: F) -> Result<Vec<Vec<T>>>
+where
+    F: Fn(char) -> Option<T>,
+{
+    lines(input)
+        .map(|line| {
+            if line.is_blank() {
+                return Err(Error::parse("empty row in grid").at(line.number, 1));
+            }
+
+            line.text
+                .chars()
+                .enumerate()
+                .map(|(col_ix, c)| {
+                    cell(c).ok_or_else(|| {
+                        Error::parse(format!("unexpected square {:?}; expected {}", c, expected))
+                            .at(line.number, col_ix + 1)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_strip_crlf_and_trailing_blank_lines() {
+        let texts: Vec<&str> = lines("a\r\nb  \r\n\r\nc\r\n\r\n\r\n").map(|line| line.text).collect();
+        assert_eq!(texts, vec!["a", "b", "", "c"]);
+    }
+
+    #[test]
+    fn records_keep_line_numbers() {
+        let records = records("\na\nb\n\n\nc\n");
+        let first_lines: Vec<usize> = records.iter().map(|record| record.first_line()).collect();
+        assert_eq!(first_lines, vec![2, 6]);
+        assert_eq!(records[0].lines.len(), 2);
+    }
+
+    #[test]
+    fn read_integers_matches_integers() {
+        let input = "3\r\n-1\r\n2\r\n\r\n";
+        let source = Source::Text(input.to_string());
+        let streamed: Vec<i64> = read_integers(source.open().unwrap(), &source).unwrap();
+        assert_eq!(streamed, integers::<i64>(input).unwrap());
+        assert_eq!(streamed, vec![3, -1, 2]);
+    }
+
+    #[test]
+    fn read_integers_rejects_blank_line_before_more_input() {
+        let source = Source::Text("1\n\n2\n".to_string());
+        let err = read_integers::<i64, _>(source.open().unwrap(), &source).unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+}
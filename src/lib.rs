@@ -0,0 +1,28 @@
+/******************************************************************************/
+/* Advent of Code 2020 solutions                                              */
+/*   Each day's parser, domain types and solvers are public, so that other    */
+/*   crates and integration tests can use them without going through the      */
+/*   runner binary.                                                           */
+/******************************************************************************/
+pub mod batch;
+pub mod bench;
+pub mod binary_boarding;
+pub mod custom_customs;
+pub mod error;
+pub mod grid;
+pub mod handheld_halting;
+pub mod handy_haversacks;
+pub mod image;
+pub mod input;
+pub mod output;
+pub mod passport_processing;
+pub mod password_philosophy;
+pub mod registry;
+pub mod report_repair;
+pub mod solution;
+pub mod toboggan_trajectory;
+pub mod verify;
+
+pub use error::{Error, ErrorKind, Result};
+pub use registry::{lookup, Entry, REGISTRY};
+pub use solution::{Answer, Part, PartAnswer, Puzzle, Solution, Solved};
@@ -0,0 +1,172 @@
+/******************************************************************************/
+/* Output: machine-readable records of each day and part's answer             */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+extern crate serde;
+extern crate serde_json;
+
+use crate::error::Result;
+use crate::solution::{Answer, Part, Solved};
+use serde::Serialize;
+use std::str::FromStr;
+
+/******************************************************************************/
+/* Constant definitions                                                       */
+/******************************************************************************/
+// FNV-1a parameters, used to hash inputs
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// the columns of the TSV format, in order
+const TSV_HEADER: [&str; 9] = [
+    "day", "title", "part", "status", "answer", "error", "parse_ns", "solve_ns", "input_hash",
+];
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> std::result::Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv"  => Ok(Format::Tsv),
+            _      => Err(format!("unknown format {:?}", format)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+// Record: the outcome of one part of one day.
+// Exactly one of answer and error is set, depending on status.
+// input_hash is only missing if the input couldn't be read.
+// parse_ns is the time taken to parse the input, which is shared by both
+//   parts of a day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub title: String,
+    pub part: u32,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+    pub input_hash: Option<String>,
+}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Hash an input with 64-bit FNV-1a, as 16 hex digits.
+// Unlike std's hashers, this is guaranteed to be stable between runs and
+//   Rust versions, so it can be compared across records.
+pub fn input_hash(input: &str) -> String {
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("{:016x}", hash)
+}
+
+// Build one record per requested part from the result of solving a day.
+// If the input couldn't be read or parsed, every part gets a record with that
+//   error. input is None if it couldn't be read at all.
+pub fn records(
+    day: u32,
+    title: &str,
+    input: Option<&str>,
+    parts: &[Part],
+    solved: &Result<Solved>,
+) -> Vec<Record> {
+    let hash = input.map(input_hash);
+    let record = |part: Part, answer: &Result<Answer>, parse_ns: u64, solve_ns: u64| Record {
+        day,
+        title: title.to_string(),
+        part: part.number(),
+        status: if answer.is_ok() { Status::Ok } else { Status::Error },
+        answer: answer.as_ref().ok().cloned(),
+        error: answer.as_ref().err().map(|err| err.to_string()),
+        parse_ns,
+        solve_ns,
+        input_hash: hash.clone(),
+    };
+
+    match solved {
+        Ok(solved) => solved
+            .answers
+            .iter()
+            .map(|answer| {
+                record(
+                    answer.part,
+                    &answer.answer,
+                    solved.parse_time.as_nanos() as u64,
+                    answer.time.as_nanos() as u64,
+                )
+            })
+            .collect(),
+        Err(err) => parts
+            .iter()
+            .map(|part| record(*part, &Err(err.clone()), 0, 0))
+            .collect(),
+    }
+}
+
+// Serialize value as pretty-printed JSON. Only for the crate's own records
+//   and reports: serializing plain structs of numbers and strings can't fail.
+pub fn to_pretty_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+// Format records as a JSON array
+pub fn to_json(records: &[Record]) -> String {
+    to_pretty_json(records)
+}
+
+// Format records as tab-separated values, with a header row.
+// Tabs and newlines inside fields are replaced with spaces.
+pub fn to_tsv(records: &[Record]) -> String {
+    let clean = |field: &str| field.replace(['\t', '\n', '\r'], " ");
+
+    let mut tsv = TSV_HEADER.join("\t");
+    tsv.push('\n');
+    for record in records.iter() {
+        let status = match record.status {
+            Status::Ok    => "ok",
+            Status::Error => "error",
+        };
+        let fields = [
+            record.day.to_string(),
+            clean(&record.title),
+            record.part.to_string(),
+            status.to_string(),
+            record.answer.as_ref().map(|answer| clean(&answer.to_string())).unwrap_or_default(),
+            record.error.as_ref().map(|err| clean(err)).unwrap_or_default(),
+            record.parse_ns.to_string(),
+            record.solve_ns.to_string(),
+            record.input_hash.clone().unwrap_or_default(),
+        ];
+        tsv.push_str(&fields.join("\t"));
+        tsv.push('\n');
+    }
+
+    tsv
+}
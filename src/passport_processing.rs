@@ -1,18 +1,18 @@
 extern crate regex;
 
+use crate::error::{column_of, Error, Result};
+use crate::input;
+use crate::solution::{Answer, Solution};
 use regex::Regex;
-use std::io::prelude::*;
-use std::fs::File;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::iter::Iterator;
 
-// configure what a blank line looks like depending on OS
-#[cfg(windows)]
-const BLANK_LINE: &'static str = "\r\n\r\n";
-#[cfg(not(windows))]
-const BLANK_LINE: &'static str = "\n\n";
+// every field that a passport must have. cid is optional, so it's not here.
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
 
-#[derive(PartialEq)]
-enum EyeColor {
+#[derive(Debug, PartialEq)]
+pub enum EyeColor {
     Amber,
     Blue,
     Brown,
@@ -23,34 +23,68 @@ enum EyeColor {
     Invalid,
 }
 
-enum HeightUnit {
+#[derive(Debug)]
+pub enum HeightUnit {
     Centimeters,
     Inches,
     Invalid,
 }
 
-struct Passport {
-    byr: u32,        // birth year
-    iyr: u32,        // issue year
-    eyr: u32,        // expiration year
-    hgt: (HeightUnit, u32),  // height (u32 is the value)
-    hcl: String,     // hair color
-    ecl: EyeColor,   // eye color
-    pid: String,     // passport ID
-    cid: u32,        // country ID. If set to 0, the "country" is North Pole.
+#[derive(Debug)]
+pub struct Passport {
+    pub byr: u32,        // birth year
+    pub iyr: u32,        // issue year
+    pub eyr: u32,        // expiration year
+    pub hgt: (HeightUnit, u32),  // height (u32 is the value)
+    pub hcl: String,     // hair color
+    pub ecl: EyeColor,   // eye color
+    pub pid: String,     // passport ID
+    pub cid: u32,        // country ID. If set to 0, the "country" is North Pole.
+    // the keys of every field present in the input, whether or not the
+    //   field's value is valid
+    pub fields: HashSet<String>,
 }
 
-pub fn run(input: &str) {
-    // todo: error handling for missing file
-    let mut file = File::open(input).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+impl Passport {
+    // Check that every required field is present, without looking at values
+    pub fn has_required_fields(&self) -> bool {
+        REQUIRED_FIELDS.iter().all(|field| self.fields.contains(*field))
+    }
+}
+
+pub struct PassportProcessing;
+
+impl Solution for PassportProcessing {
+    type Input = Vec<Passport>;
+
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Passport Processing";
+
+    fn parse(&self, input: &str) -> Result<Vec<Passport>> {
+        build_passports(input)
+    }
+
+    // part 1: count the passports that have every required field
+    fn part1(&self, passports: &Vec<Passport>) -> Result<Answer> {
+        let count = passports
+            .iter()
+            .filter(|passport| passport.has_required_fields())
+            .count();
+        Answer::try_from(count)
+    }
 
+    // part 2: count the passports whose required fields are also valid
+    fn part2(&self, passports: &Vec<Passport>) -> Result<Answer> {
+        Ok(Answer::from(count_valid_passports(passports)))
+    }
+}
+
+// Construct and return a vec of Passport structs from the input string
+pub fn build_passports(input: &str) -> Result<Vec<Passport>> {
     let mut passports: Vec<Passport> = Vec::new();
     // construct passports from lines
-    // each passport is separated by a blank line,
-    //   so split lines along newline pairs
-    for input_passport in contents.split(BLANK_LINE) {
+    // each passport is separated by a blank line
+    for input_passport in input::records(input) {
         // start with an empty passport
         // unfortunately, enums don't support the Default trait, so we can't
         //   just use Default::default().
@@ -63,80 +97,99 @@ pub fn run(input: &str) {
             ecl: EyeColor::Invalid,
             pid: String::new(),
             cid: 0,
+            fields: HashSet::new(),
         };
 
-        for field in input_passport.split_whitespace() {
-            // for each field, the key and value are separated by a colon
-            // i recognize that i really should be serializing this into json,
-            //   but i'm not good enough at rust for that yet.
-            let key_val: Vec<&str> = field.split(':').collect();
-            // key_val.get(0) is the key
-            if let Some(key) = key_val.get(0) {
-                // key_val.get(1) is the value
-                if let Some(val) = key_val.get(1) {
-                    match *key {
-                        "byr" => passport.byr = val.parse::<u32>().unwrap_or_default(),
-                        "iyr" => passport.iyr = val.parse::<u32>().unwrap_or_default(),
-                        "eyr" => passport.eyr = val.parse::<u32>().unwrap_or_default(),
-                        "hgt" => {
-                            if val.ends_with("cm") {
-                                // get the value before the "cm" by slicing the
-                                //   string at the "cm"
-                                if let Some(unit_suffix_ix) = val.find("cm") {
-                                    passport.hgt = (
-                                        HeightUnit::Centimeters,
-                                        // get the value before the "cm"
-                                        val[0..unit_suffix_ix].parse::<u32>().unwrap_or_default(),
-                                    );    
-                                }
-                            } else if val.ends_with("in") {
-                                // get the value before the "in" by slicing the
-                                //   string at the "in"
-                                if let Some(unit_suffix_ix) = val.find("in") {
-                                    passport.hgt = (
-                                        HeightUnit::Inches,
-                                        // get the value before the "cm"
-                                        val[0..unit_suffix_ix].parse::<u32>().unwrap_or_default(),
-                                    );    
-                                }
-                            } else {
-                                // invalid height string
-                                passport.hgt = (HeightUnit::Invalid, 0);
+        for line in input_passport.lines.iter() {
+            let (line_no, passport_line) = (line.number, line.text);
+            for field in passport_line.split_whitespace() {
+                // for each field, the key and value are separated by a colon
+                // i recognize that i really should be serializing this into json,
+                //   but i'm not good enough at rust for that yet.
+                let mut key_val = field.splitn(2, ':');
+                // the first item is the key, which is always present
+                let key = key_val.next().unwrap_or_default();
+                // the second item is the value
+                let val = match key_val.next() {
+                    Some(val) => val,
+                    None => {
+                        return Err(Error::parse(format!("no value for field {:?}", field))
+                            .at(line_no, column_of(passport_line, field)));
+                    }
+                };
+
+                passport.fields.insert(key.to_string());
+                // a malformed number isn't a parse error: the puzzle input is
+                //   full of invalid values, which part 2 has to count as
+                //   invalid. So a number that doesn't parse becomes 0 on
+                //   purpose, which is outside every valid range.
+                match key {
+                    "byr" => passport.byr = val.parse::<u32>().unwrap_or_default(),
+                    "iyr" => passport.iyr = val.parse::<u32>().unwrap_or_default(),
+                    "eyr" => passport.eyr = val.parse::<u32>().unwrap_or_default(),
+                    "hgt" => {
+                        if val.ends_with("cm") {
+                            // get the value before the "cm" by slicing the
+                            //   string at the "cm"
+                            if let Some(unit_suffix_ix) = val.find("cm") {
+                                passport.hgt = (
+                                    HeightUnit::Centimeters,
+                                    // get the value before the "cm"
+                                    val[0..unit_suffix_ix].parse::<u32>().unwrap_or_default(),
+                                );    
                             }
-                        },
-                        "ecl" => {
-                            passport.ecl = match *val {
-                                "amb" => EyeColor::Amber,
-                                "blu" => EyeColor::Blue,
-                                "brn" => EyeColor::Brown,
-                                "gry" => EyeColor::Gray,
-                                "grn" => EyeColor::Green,
-                                "hzl" => EyeColor::Hazel,
-                                "oth" => EyeColor::Other,
-                                _     => EyeColor::Invalid,
+                        } else if val.ends_with("in") {
+                            // get the value before the "in" by slicing the
+                            //   string at the "in"
+                            if let Some(unit_suffix_ix) = val.find("in") {
+                                passport.hgt = (
+                                    HeightUnit::Inches,
+                                    // get the value before the "in"
+                                    val[0..unit_suffix_ix].parse::<u32>().unwrap_or_default(),
+                                );    
                             }
+                        } else {
+                            // invalid height string
+                            passport.hgt = (HeightUnit::Invalid, 0);
+                        }
+                    },
+                    "ecl" => {
+                        passport.ecl = match val {
+                            "amb" => EyeColor::Amber,
+                            "blu" => EyeColor::Blue,
+                            "brn" => EyeColor::Brown,
+                            "gry" => EyeColor::Gray,
+                            "grn" => EyeColor::Green,
+                            "hzl" => EyeColor::Hazel,
+                            "oth" => EyeColor::Other,
+                            _     => EyeColor::Invalid,
                         }
-                        "hcl" => passport.hcl = val.to_string(),
-                        "pid" => passport.pid = val.to_string(),
-                        "cid" => passport.cid = val.parse::<u32>().unwrap_or_default(),
-                        _     => println!("Error! Found unknown key {}", key),
                     }
-                } else {
-                    println!("Error; no value for field {}", field);
+                    "hcl" => passport.hcl = val.to_string(),
+                    "pid" => passport.pid = val.to_string(),
+                    "cid" => passport.cid = val.parse::<u32>().unwrap_or_default(),
+                    _     => {
+                        return Err(Error::parse(format!("unknown key {:?}", key))
+                            .at(line_no, column_of(passport_line, field)));
+                    }
                 }
-            } else {
-                println!("Error; no key for field {}", field);
             }
         }
 
         passports.push(passport);
     }
 
-    // Now we've built all the passports. Validate them and count the valid ones
+    Ok(passports)
+}
+
+// Validate the passports and count the valid ones
+pub fn count_valid_passports(passports: &[Passport]) -> u32 {
     let mut valid_passports = 0;
     // this regex matches hex values starting with a pound sign and
     //   followed by 6 hex digits. Used to validate hair color.
-    let hex_color_regex = Regex::new(r"#([a-f0-9]){6}").unwrap();
+    let hex_color_regex = Regex::new(r"^#([a-f0-9]){6}$").unwrap();
+    // passport IDs are nine digits, including leading zeroes
+    let pid_regex = Regex::new(r"^[0-9]{9}$").unwrap();
 
     for passport in passports.iter() {
         // make sure that all fields match expected values.
@@ -145,13 +198,13 @@ pub fn run(input: &str) {
         //   implement an Iterator for Passport, and some kind of rules engine
         //   or something. but this is just a silly daily programming challenge.
 
+        if !passport.has_required_fields() { continue; }
+
         if !((1920..=2002).contains(&passport.byr)) { continue; }
         if !((2010..=2020).contains(&passport.iyr)) { continue; }
         if !((2020..=2030).contains(&passport.eyr)) { continue; }
 
-        if passport.pid.len() != 9 {
-            continue;
-        }
+        if !(pid_regex.is_match(passport.pid.as_str())) { continue; }
 
         match passport.hgt.0 {
             HeightUnit::Centimeters => {
@@ -171,5 +224,58 @@ pub fn run(input: &str) {
         valid_passports += 1;
     }
 
-    println!("Valid passports: {}", valid_passports);
-}
\ No newline at end of file
+    valid_passports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a passport that passes every check, from the puzzle's valid examples
+    const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+
+    // whether VALID is still valid with one field replaced
+    fn is_valid_with(key: &str, val: &str) -> bool {
+        let fields: Vec<String> = VALID
+            .split_whitespace()
+            .map(|field| {
+                if field.starts_with(key) {
+                    format!("{}:{}", key, val)
+                } else {
+                    field.to_string()
+                }
+            })
+            .collect();
+        count_valid_passports(&build_passports(&fields.join(" ")).unwrap()) == 1
+    }
+
+    #[test]
+    fn example_field_rules() {
+        assert!(is_valid_with("byr", "2002"));
+        assert!(!is_valid_with("byr", "2003"));
+        assert!(is_valid_with("hgt", "60in"));
+        assert!(is_valid_with("hgt", "190cm"));
+        assert!(!is_valid_with("hgt", "190in"));
+        assert!(!is_valid_with("hgt", "190"));
+        assert!(is_valid_with("hcl", "#123abc"));
+        assert!(!is_valid_with("hcl", "#123abz"));
+        assert!(!is_valid_with("hcl", "123abc"));
+        assert!(is_valid_with("ecl", "brn"));
+        assert!(!is_valid_with("ecl", "wat"));
+        assert!(is_valid_with("pid", "000000001"));
+        assert!(!is_valid_with("pid", "0123456789"));
+    }
+
+    #[test]
+    fn cid_is_optional() {
+        let passports = build_passports(include_str!("../examples/day04/example.txt")).unwrap();
+        let required: Vec<bool> = passports.iter().map(|p| p.has_required_fields()).collect();
+        assert_eq!(required, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn unknown_key_is_located() {
+        let err = build_passports("byr:1980\n\niyr:2012 xyz:1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(3), Some(10)));
+    }
+}
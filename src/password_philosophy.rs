@@ -1,61 +1,1287 @@
-use std::io::prelude::*;
-use std::fs::File;
-
-struct PasswordPolicy {
-    first_ix: usize,
-    second_ix: usize,
-    letter: char,
-}
-
-struct InputLine<'a> {
-    policy: PasswordPolicy,
-    password: &'a str,
-}
-
-pub fn run(input: &str) {
-    // todo: error handling for missing file
-    let mut file = File::open(input).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-
-    // construct data structure from lines
-    let mut input_lines: Vec<InputLine> = Vec::new();
-    for line in contents.lines() {
-        // split at the colon-and-space
-        // this givs us the policy and password separated
-        let line_sections: Vec<&str> = line.split(": ").collect();
-
-        // parse the policy into parts separated by - and a space.
-        // syntax: <min>-<max> <letter>
-        let policy: Vec<&str> = line_sections[0].split(|c| c == '-' || c == ' ').collect();
-        input_lines.push(InputLine {
-           policy: PasswordPolicy {
-               first_ix: policy[0].parse().unwrap(),
-               second_ix: policy[1].parse().unwrap(),
-               letter: policy[2].chars().nth(0).unwrap(),
-           },
-           password: line_sections[1],
-        });
-    }
-
-    // count valid passwords
-    let mut count = 0;
+extern crate nom;
+extern crate serde;
+extern crate unicode_segmentation;
+
+use nom::{
+    character::complete::{anychar, char, digit1},
+    error::ErrorKind,
+    map_res,
+    named
+};
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::output;
+use crate::solution::{Answer, Solution};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::Peekable;
+use std::slice;
+use std::str::{self, CharIndices, FromStr};
+use unicode_segmentation::{Graphemes, UnicodeSegmentation};
+
+// the columns of the audit report's CSV format, in order
+const AUDIT_CSV_HEADER: [&str; 9] = [
+    "line", "policy", "rule", "password", "valid", "letter_count", "first_char", "second_char",
+    "reason",
+];
+
+// The two numbers in a policy mean different things depending on which kind
+//   of policy the line is read as; see PolicyKind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub first_ix: usize,
+    pub second_ix: usize,
+    // a single unit of the line's Indexing
+    pub letter: String,
+}
+
+// LinePolicy: the policy at the start of a line of the database
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinePolicy {
+    // the original "<first>-<second> <letter>" syntax, which means something
+    //   different for each kind of policy
+    Plain(PasswordPolicy),
+    // a policy expression, which means the same whichever kind is chosen
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+pub struct InputLine {
+    // the 1-based line number in the database
+    pub line_no: usize,
+    pub policy: LinePolicy,
+    pub password: String,
+    // how the policy's positions, counts and letters were meant
+    pub indexing: Indexing,
+    // how to check a plain policy's numbers when they're read as positions
+    pub range_check: RangeCheck,
+    // the 1-based columns of a plain policy's two numbers, for errors
+    number_columns: [usize; 2],
+}
+
+// RangeCheck: what to do with a line whose policy numbers are past the end of
+//   its password
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeCheck {
+    // reject the line as invalid input
+    Strict,
+    // accept the line; a position past the end never holds the letter
+    Lenient,
+}
+
+// Indexing: the units a password is split into, which positions and lengths
+//   count and which a letter must be one of
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indexing {
+    // UTF-8 bytes. A letter must be a single byte, i.e. ASCII.
+    Byte,
+    // Unicode scalar values, i.e. Rust chars. This is what the puzzle means.
+    Char,
+    // extended grapheme clusters, i.e. what a reader sees as one character,
+    //   even if it's made of several chars like "e\u{301}" or an emoji with a
+    //   skin tone
+    Grapheme,
+}
+
+// ParseOptions: how to read the lines of the database
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub range_check: RangeCheck,
+    pub indexing: Indexing,
+}
+
+// Units: the units of a password under some Indexing, each as its bytes
+enum Units<'a> {
+    Bytes(slice::Chunks<'a, u8>),
+    Chars(&'a str, CharIndices<'a>),
+    Graphemes(Graphemes<'a>),
+}
+
+// Policy: a rule a password must follow to be valid
+pub trait Policy {
+    fn accepts(&self, password: &str, indexing: Indexing) -> bool;
+    // Check password against the rule, keeping what was found so that the
+    //   verdict can be explained. Slower than accepts.
+    fn check(&self, password: &str, indexing: Indexing) -> Check;
+}
+
+// Finding: what a policy looked at in a password to decide whether it's valid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Finding {
+    // how many times letter appears
+    LetterCount { letter: Letter, count: usize },
+    // the unit at each 1-based position, or None if it's past the end
+    Positions { letter: Letter, found: Vec<(usize, Option<String>)> },
+    // how many units long the password is
+    Length(usize),
+    // the check of each part of an AND or OR expression, with the part's
+    //   rule written out
+    Parts(Vec<(String, Check)>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub valid: bool,
+    pub finding: Finding,
+}
+
+// AuditEntry: the verdict of one policy on one line of the database, and why
+//   it was reached. letter_count is only set by the count range policy, and
+//   first_char and second_char by the positional policy. A line with a policy
+//   expression gets a single entry, with the policy "expression".
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AuditEntry {
+    pub line: usize,
+    pub policy: &'static str,
+    pub rule: String,
+    pub password: String,
+    pub valid: bool,
+    pub letter_count: Option<usize>,
+    pub first_char: Option<String>,
+    pub second_char: Option<String>,
+    pub reason: String,
+}
+
+// Letter: what a policy looks for in a password
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Letter {
+    // exactly this unit
+    Exact(String),
+    // any single char in one of the inclusive ranges, or with negated set,
+    //   any single char in none of them; written like a regex class, e.g.
+    //   "[a-z0-9]". A grapheme made of several chars never matches.
+    Class { ranges: Vec<(char, char)>, negated: bool },
+}
+
+// CountRange: the sled rental policy. letter must appear between min and max
+//   times, inclusive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CountRange {
+    pub min: usize,
+    pub max: usize,
+    pub letter: Letter,
+}
+
+// Positional: the Official Toboggan Corporate Policy. Exactly one of the
+//   1-based positions first and second must hold letter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Positional {
+    pub first: usize,
+    pub second: usize,
+    pub letter: Letter,
+}
+
+// LengthRange: the password must be between min and max units long,
+//   inclusive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthRange {
+    pub min: usize,
+    pub max: usize,
+}
+
+// Expr: a policy expression, combining the rules above.
+// syntax, from loosest to tightest binding:
+//   <expr> OR <expr>
+//   <expr> AND <expr>
+//   NOT <expr>
+//   ( <expr> )
+//   <min>-<max> <letter>      a CountRange
+//   <first>,<second> <letter> a Positional
+//   LEN <min>-<max>           a LengthRange
+// where <letter> is a single unit or a class like "[a-z]" or "[^aeiou]".
+//   In a class, a '\' makes the char after it literal, e.g. "[\]\-]". A
+//   class has to end before the ": " in front of the password; if it doesn't,
+//   its '[' is just a letter.
+//   The original "<first>-<second> <letter>" syntax is a CountRange on its
+//   own, but is kept as a LinePolicy::Plain, so it can still be read as
+//   either kind of policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Count(CountRange),
+    Positions(Positional),
+    Length(LengthRange),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+// PolicyKind: which policy to read a database line's numbers as
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyKind {
+    CountRange,
+    Positional,
+}
+
+impl Indexing {
+    pub const ALL: [Indexing; 3] = [Indexing::Byte, Indexing::Char, Indexing::Grapheme];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Indexing::Byte     => "byte",
+            Indexing::Char     => "char",
+            Indexing::Grapheme => "grapheme",
+        }
+    }
+
+    // Split text into units
+    fn units(self, text: &str) -> Units<'_> {
+        match self {
+            Indexing::Byte     => Units::Bytes(text.as_bytes().chunks(1)),
+            Indexing::Char     => Units::Chars(text, text.char_indices()),
+            Indexing::Grapheme => Units::Graphemes(text.graphemes(true)),
+        }
+    }
+
+    // How many units long text is
+    pub fn length(self, text: &str) -> usize {
+        match self {
+            Indexing::Byte => text.len(),
+            _ => self.units(text).count(),
+        }
+    }
+
+    // Get the unit at 1-based position ix of text, if there is one
+    fn unit_at(self, text: &str, ix: usize) -> Option<&[u8]> {
+        ix.checked_sub(1).and_then(|ix| self.units(text).nth(ix))
+    }
+}
+
+impl FromStr for Indexing {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Indexing::ALL
+            .iter()
+            .copied()
+            .find(|indexing| indexing.name() == name)
+            .ok_or_else(|| Error::parse(format!("unknown indexing {:?}", name)))
+    }
+}
+
+impl Default for ParseOptions {
+    // what the puzzle means
+    fn default() -> Self {
+        ParseOptions { range_check: RangeCheck::Strict, indexing: Indexing::Char }
+    }
+}
+
+impl<'a> Iterator for Units<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        match self {
+            Units::Bytes(bytes) => bytes.next(),
+            Units::Chars(text, chars) => {
+                let (start, c) = chars.next()?;
+                Some(&text.as_bytes()[start..start + c.len_utf8()])
+            }
+            Units::Graphemes(graphemes) => graphemes.next().map(str::as_bytes),
+        }
+    }
+}
+
+// Show a unit as text. A byte that's part of a multi-byte char isn't valid
+//   UTF-8 on its own, so it's shown escaped.
+fn unit_text(unit: &[u8]) -> String {
+    match str::from_utf8(unit) {
+        Ok(text) => text.to_string(),
+        Err(_) => unit.iter().map(|byte| format!("\\x{:02x}", byte)).collect(),
+    }
+}
+
+impl Letter {
+    pub fn matches(&self, unit: &[u8]) -> bool {
+        match self {
+            Letter::Exact(letter) => unit == letter.as_bytes(),
+            Letter::Class { ranges, negated } => {
+                let mut chars = str::from_utf8(unit).unwrap_or_default().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => {
+                        ranges.iter().any(|(low, high)| (*low..=*high).contains(&c)) != *negated
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    // how to show the letter in an explanation: quoted if it's a single unit
+    fn quoted(&self) -> String {
+        match self {
+            Letter::Exact(letter) => quote(letter),
+            Letter::Class { .. } => self.to_string(),
+        }
+    }
+}
+
+// Quote a unit for an explanation, as Debug would quote a char
+fn quote(unit: &str) -> String {
+    format!("'{}'", unit.escape_debug())
+}
+
+impl Policy for CountRange {
+    fn accepts(&self, password: &str, indexing: Indexing) -> bool {
+        let count = indexing.units(password).filter(|unit| self.letter.matches(unit)).count();
+        (self.min..=self.max).contains(&count)
+    }
+
+    fn check(&self, password: &str, indexing: Indexing) -> Check {
+        let count = indexing.units(password).filter(|unit| self.letter.matches(unit)).count();
+        Check {
+            valid: (self.min..=self.max).contains(&count),
+            finding: Finding::LetterCount { letter: self.letter.clone(), count },
+        }
+    }
+}
+
+impl Policy for Positional {
+    fn accepts(&self, password: &str, indexing: Indexing) -> bool {
+        let holds_letter = |ix: usize| {
+            indexing.unit_at(password, ix).is_some_and(|unit| self.letter.matches(unit))
+        };
+        holds_letter(self.first) != holds_letter(self.second)
+    }
+
+    fn check(&self, password: &str, indexing: Indexing) -> Check {
+        let found = |ix: usize| (ix, indexing.unit_at(password, ix).map(unit_text));
+        Check {
+            valid: self.accepts(password, indexing),
+            finding: Finding::Positions {
+                letter: self.letter.clone(),
+                found: vec![found(self.first), found(self.second)],
+            },
+        }
+    }
+}
+
+impl Policy for LengthRange {
+    fn accepts(&self, password: &str, indexing: Indexing) -> bool {
+        (self.min..=self.max).contains(&indexing.length(password))
+    }
+
+    fn check(&self, password: &str, indexing: Indexing) -> Check {
+        let length = indexing.length(password);
+        Check { valid: (self.min..=self.max).contains(&length), finding: Finding::Length(length) }
+    }
+}
+
+impl Policy for Expr {
+    fn accepts(&self, password: &str, indexing: Indexing) -> bool {
+        match self {
+            Expr::Count(rule)     => rule.accepts(password, indexing),
+            Expr::Positions(rule) => rule.accepts(password, indexing),
+            Expr::Length(rule)    => rule.accepts(password, indexing),
+            Expr::Not(expr)       => !expr.accepts(password, indexing),
+            Expr::And(exprs)      => exprs.iter().all(|expr| expr.accepts(password, indexing)),
+            Expr::Or(exprs)       => exprs.iter().any(|expr| expr.accepts(password, indexing)),
+        }
+    }
+
+    fn check(&self, password: &str, indexing: Indexing) -> Check {
+        // check every part, even once the outcome is known, so that the
+        //   explanation is complete
+        let parts = |exprs: &[Expr]| {
+            exprs
+                .iter()
+                .map(|expr| (expr.to_string(), expr.check(password, indexing)))
+                .collect::<Vec<_>>()
+        };
+
+        match self {
+            Expr::Count(rule)     => rule.check(password, indexing),
+            Expr::Positions(rule) => rule.check(password, indexing),
+            Expr::Length(rule)    => rule.check(password, indexing),
+            // what was found is the same, just with the opposite verdict
+            Expr::Not(expr) => {
+                let check = expr.check(password, indexing);
+                Check { valid: !check.valid, finding: check.finding }
+            }
+            Expr::And(exprs) => {
+                let parts = parts(exprs);
+                Check {
+                    valid: parts.iter().all(|(_, check)| check.valid),
+                    finding: Finding::Parts(parts),
+                }
+            }
+            Expr::Or(exprs) => {
+                let parts = parts(exprs);
+                Check {
+                    valid: parts.iter().any(|(_, check)| check.valid),
+                    finding: Finding::Parts(parts),
+                }
+            }
+        }
+    }
+}
+
+// written as it would be in a policy expression, e.g. "a" or "[^a-z]"
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Letter::Exact(letter) => write!(f, "{}", letter),
+            Letter::Class { ranges, negated } => {
+                // escape the chars that would mean something else, so that
+                //   the class always parses back the same
+                let escaped = |c: char| match c {
+                    ']' | '^' | '-' | '\\' => format!("\\{}", c),
+                    _ => c.to_string(),
+                };
+                write!(f, "[{}", if *negated { "^" } else { "" })?;
+                for (low, high) in ranges.iter() {
+                    if low == high {
+                        write!(f, "{}", escaped(*low))?;
+                    } else {
+                        write!(f, "{}-{}", escaped(*low), escaped(*high))?;
+                    }
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+// written as in a policy expression, e.g. "1-3 a"
+impl fmt::Display for CountRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{} {}", self.min, self.max, self.letter)
+    }
+}
+
+// written as in a policy expression, with a comma to tell it apart from a
+//   count range, e.g. "1,3 a"
+impl fmt::Display for Positional {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{} {}", self.first, self.second, self.letter)
+    }
+}
+
+impl fmt::Display for LengthRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LEN {}-{}", self.min, self.max)
+    }
+}
+
+// written as a policy expression, with parentheses only where they're needed
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // write the parts of an AND or OR, wrapping any that bind more
+        //   loosely than the expression they're in
+        let join = |f: &mut fmt::Formatter, exprs: &[Expr], op: &str, wrap_or: bool| {
+            for (ix, expr) in exprs.iter().enumerate() {
+                if ix > 0 {
+                    write!(f, " {} ", op)?;
+                }
+                match expr {
+                    Expr::Or(_) if wrap_or => write!(f, "({})", expr)?,
+                    _ => write!(f, "{}", expr)?,
+                }
+            }
+            Ok(())
+        };
+
+        match self {
+            Expr::Count(rule)     => write!(f, "{}", rule),
+            Expr::Positions(rule) => write!(f, "{}", rule),
+            Expr::Length(rule)    => write!(f, "{}", rule),
+            Expr::Not(expr) => match **expr {
+                Expr::And(_) | Expr::Or(_) => write!(f, "NOT ({})", expr),
+                _ => write!(f, "NOT {}", expr),
+            },
+            Expr::And(exprs) => join(f, exprs, "AND", true),
+            Expr::Or(exprs)  => join(f, exprs, "OR", false),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Finding::LetterCount { letter, count } => {
+                let times = if *count == 1 { "time" } else { "times" };
+                write!(f, "{} appears {} {}", letter.quoted(), count, times)
+            }
+            Finding::Positions { letter, found } => {
+                let matches = found
+                    .iter()
+                    .filter(|(_, unit)| unit.as_ref().is_some_and(|unit| letter.matches(unit.as_bytes())))
+                    .count();
+                for (ix, unit) in found.iter() {
+                    match unit {
+                        Some(unit) => write!(f, "position {} is {}; ", ix, quote(unit))?,
+                        None       => write!(f, "position {} is past the end; ", ix)?,
+                    }
+                }
+                let verb = if matches == 1 { "is" } else { "are" };
+                write!(f, "{} of them {} {}", matches, verb, letter.quoted())
+            }
+            Finding::Length(length) => write!(f, "length is {}", length),
+            Finding::Parts(parts) => {
+                for (ix, (rule, check)) in parts.iter().enumerate() {
+                    if ix > 0 {
+                        write!(f, "; ")?;
+                    }
+                    let verdict = if check.valid { "passes" } else { "fails" };
+                    match check.finding {
+                        Finding::Parts(_) => write!(f, "{} {} ({})", rule, verdict, check.finding)?,
+                        _ => write!(f, "{} {}: {}", rule, verdict, check.finding)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PolicyKind {
+    // every kind, in the order of the parts that use them
+    pub const ALL: [PolicyKind; 2] = [PolicyKind::CountRange, PolicyKind::Positional];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PolicyKind::CountRange => "count",
+            PolicyKind::Positional => "position",
+        }
+    }
+}
+
+impl FromStr for PolicyKind {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        PolicyKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| Error::parse(format!("unknown policy {:?}", name)))
+    }
+}
+
+impl PasswordPolicy {
+    pub fn count_range(&self) -> CountRange {
+        let letter = Letter::Exact(self.letter.clone());
+        CountRange { min: self.first_ix, max: self.second_ix, letter }
+    }
+
+    pub fn positional(&self) -> Positional {
+        let letter = Letter::Exact(self.letter.clone());
+        Positional { first: self.first_ix, second: self.second_ix, letter }
+    }
+
+    // This policy, read as the given kind
+    pub fn as_expr(&self, kind: PolicyKind) -> Expr {
+        match kind {
+            PolicyKind::CountRange => Expr::Count(self.count_range()),
+            PolicyKind::Positional => Expr::Positions(self.positional()),
+        }
+    }
+}
+
+impl LinePolicy {
+    // The expression this policy means when read as the given kind. An
+    //   expression means the same for every kind.
+    pub fn as_expr(&self, kind: PolicyKind) -> Cow<'_, Expr> {
+        match self {
+            LinePolicy::Plain(policy) => Cow::Owned(policy.as_expr(kind)),
+            LinePolicy::Expr(expr) => Cow::Borrowed(expr),
+        }
+    }
+
+    // Check password against this policy, read as the given kind
+    pub fn accepts(&self, kind: PolicyKind, password: &str, indexing: Indexing) -> bool {
+        self.as_expr(kind).accepts(password, indexing)
+    }
+
+    // Check password against this policy as accepts does, explaining why
+    pub fn check(&self, kind: PolicyKind, password: &str, indexing: Indexing) -> Check {
+        self.as_expr(kind).check(password, indexing)
+    }
+
+    // The rule this policy gives when read as the given kind, e.g. "1-3 a"
+    pub fn rule(&self, kind: PolicyKind) -> String {
+        self.as_expr(kind).to_string()
+    }
+}
+
+// Parse one policy number
+named!(parse_number<&str, usize>,
+    map_res!(
+        // use the "complete" digit parser, so a number at the end of the
+        //   input isn't treated as incomplete
+        digit1,
+        |digits: &str| digits.parse::<usize>()
+    )
+);
+
+// ExprParser: a recursive descent parser for policy expressions, which works
+//   through a line from the start. rest is the part not parsed yet.
+struct ExprParser<'a> {
+    line_no: usize,
+    line: &'a str,
+    rest: &'a str,
+    // what a single letter is
+    indexing: Indexing,
+    // every position named by a Positional, and the column it's at, so they
+    //   can be checked once the password is known
+    positions: Vec<(usize, usize)>,
+}
+
+impl<'a> ExprParser<'a> {
+    fn new(line_no: usize, line: &'a str, indexing: Indexing) -> Self {
+        ExprParser { line_no, line, rest: line, indexing, positions: Vec::new() }
+    }
+
+    // the 1-based column of the start of rest
+    fn column(&self) -> usize {
+        self.line.len() - self.rest.len() + 1
+    }
+
+    // build an error pointing at the start of rest
+    fn fail(&self, expected: &str) -> Error {
+        Error::parse(format!("expected {}", expected)).at(self.line_no, self.column())
+    }
+
+    fn skip_spaces(&mut self) {
+        self.rest = self.rest.trim_start_matches(' ');
+    }
+
+    // Consume c, which must come next
+    fn expect(&mut self, c: char, expected: &str) -> Result<()> {
+        let (rest, _) = char::<_, (&str, ErrorKind)>(c)(self.rest)
+            .map_err(|_| self.fail(expected))?;
+        self.rest = rest;
+        Ok(())
+    }
+
+    // Consume keyword if it comes next as a whole word
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.rest.strip_prefix(keyword) {
+            Some(rest) if rest.starts_with([' ', '(']) => {
+                self.rest = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn number(&mut self) -> Result<usize> {
+        let (rest, number) = parse_number(self.rest).map_err(|_| self.fail("a number"))?;
+        self.rest = rest;
+        Ok(number)
+    }
+
+    // <and> [OR <and>]...
+    fn or(&mut self) -> Result<Expr> {
+        let mut exprs: Vec<Expr> = vec![self.and()?];
+        loop {
+            self.skip_spaces();
+            if !self.keyword("OR") {
+                break;
+            }
+            self.skip_spaces();
+            exprs.push(self.and()?);
+        }
+        Ok(if exprs.len() == 1 { exprs.pop().unwrap() } else { Expr::Or(exprs) })
+    }
+
+    // <not> [AND <not>]...
+    fn and(&mut self) -> Result<Expr> {
+        let mut exprs: Vec<Expr> = vec![self.not()?];
+        loop {
+            self.skip_spaces();
+            if !self.keyword("AND") {
+                break;
+            }
+            self.skip_spaces();
+            exprs.push(self.not()?);
+        }
+        Ok(if exprs.len() == 1 { exprs.pop().unwrap() } else { Expr::And(exprs) })
+    }
+
+    // [NOT]... <atom>
+    fn not(&mut self) -> Result<Expr> {
+        if self.keyword("NOT") {
+            self.skip_spaces();
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.atom()
+    }
+
+    // a parenthesised expression, or a single rule
+    fn atom(&mut self) -> Result<Expr> {
+        if let Some(rest) = self.rest.strip_prefix('(') {
+            self.rest = rest;
+            self.skip_spaces();
+            let expr = self.or()?;
+            self.skip_spaces();
+            self.expect(')', "')'")?;
+            return Ok(expr);
+        }
+
+        if self.keyword("LEN") {
+            self.skip_spaces();
+            let min = self.number()?;
+            self.expect('-', "'-'")?;
+            let max = self.number()?;
+            return Ok(Expr::Length(LengthRange { min, max }));
+        }
+
+        let first_column = self.column();
+        let first = self.number()?;
+        let positional = self.rest.starts_with(',');
+        if !positional && !self.rest.starts_with('-') {
+            return Err(self.fail("'-' or ','"));
+        }
+        self.rest = &self.rest[1..];
+        let second_column = self.column();
+        let second = self.number()?;
+        self.expect(' ', "a space")?;
+        let letter = self.letter()?;
+
+        if !positional {
+            return Ok(Expr::Count(CountRange { min: first, max: second, letter }));
+        }
+        for (ix, column) in [(first, first_column), (second, second_column)].iter() {
+            if *ix == 0 {
+                return Err(Error::parse("positions start at 1").at(self.line_no, *column));
+            }
+            self.positions.push((*ix, *column));
+        }
+        Ok(Expr::Positions(Positional { first, second, letter }))
+    }
+
+    // a class like "[a-z]", or any single unit. A '[' with no closing ']' is
+    //   just a '['.
+    fn letter(&mut self) -> Result<Letter> {
+        if let Some(class) = self.class()? {
+            return Ok(class);
+        }
+
+        let (rest, letter) = match self.indexing {
+            // a grapheme can be several chars, so take all of them
+            Indexing::Grapheme => self
+                .rest
+                .graphemes(true)
+                .next()
+                .map(|letter| (&self.rest[letter.len()..], letter))
+                .ok_or_else(|| self.fail("a letter"))?,
+            Indexing::Char | Indexing::Byte => {
+                let (rest, letter) = anychar::<_, (&str, ErrorKind)>(self.rest)
+                    .map_err(|_| self.fail("a letter"))?;
+                (rest, &self.rest[..letter.len_utf8()])
+            }
+        };
+        if self.indexing == Indexing::Byte && letter.len() != 1 {
+            return Err(Error::parse(format!(
+                "{:?} is more than one byte, so it can't be a letter when indexing by byte",
+                letter
+            ))
+            .at(self.line_no, self.column()));
+        }
+
+        self.rest = rest;
+        Ok(Letter::Exact(letter.to_string()))
+    }
+
+    // [[^]<low>[-<high>]...]
+    fn class(&mut self) -> Result<Option<Letter>> {
+        let rest = match self.rest.strip_prefix('[') {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+        // so that "1-3 [: a]b" is still the letter '[' and the password "a]b"
+        let end = rest.find(": ").unwrap_or(rest.len());
+        let mut chars = rest[..end].char_indices().peekable();
+        let negated = chars.next_if(|(_, c)| *c == '^').is_some();
+
+        let mut ranges: Vec<(char, char)> = Vec::new();
+        while let Some((ix, low)) = chars.next() {
+            if low == ']' && !ranges.is_empty() {
+                self.rest = &self.rest[1 + ix + 1..];
+                return Ok(Some(Letter::Class { ranges, negated }));
+            }
+            let low = match unescape(&mut chars, low) {
+                Some(low) => low,
+                None => break,
+            };
+            // a '-' just before the ']' is a literal '-', after a single char
+            let high = match (chars.next_if(|(_, c)| *c == '-'), chars.peek()) {
+                (Some(_), Some((_, high))) if *high != ']' => {
+                    let high = chars.next().unwrap().1;
+                    match unescape(&mut chars, high) {
+                        Some(high) => high,
+                        None => break,
+                    }
+                }
+                (Some(_), _) => {
+                    ranges.push((low, low));
+                    ranges.push(('-', '-'));
+                    continue;
+                }
+                (None, _) => low,
+            };
+            if high < low {
+                return Err(Error::parse(format!("class range {}-{} is backwards", low, high))
+                    .at(self.line_no, self.column() + 1 + ix));
+            }
+            ranges.push((low, high));
+        }
+
+        Ok(None)
+    }
+}
+
+// The char c in a class, reading the char after it instead if c is a '\'.
+//   None if there's nothing after the '\'.
+fn unescape(chars: &mut Peekable<CharIndices>, c: char) -> Option<char> {
+    if c == '\\' {
+        chars.next().map(|(_, c)| c)
+    } else {
+        Some(c)
+    }
+}
+
+// Parse one line of the password database. line_no is the 1-based line
+//   number, used for error reporting.
+// syntax: <policy>: <password>
+//   where <policy> is either <first>-<second> <letter>, or an expression as
+//   described by Expr
+fn parse_line(line_no: usize, line: &str, options: ParseOptions) -> Result<InputLine> {
+    let mut parser = ExprParser::new(line_no, line, options.indexing);
+    let expr = parser.or()?;
+    let password = parser
+        .rest
+        .strip_prefix(": ")
+        .ok_or_else(|| parser.fail("\": \" before the password"))?;
+
+    // a lone count range with a single letter, written without parentheses,
+    //   is the original syntax. Its numbers are only positions when it's read
+    //   as a Positional, so they're checked then, by InputLine::check_reading
+    let mut number_columns = [0; 2];
+    let policy = match expr {
+        Expr::Count(CountRange { min, max, letter: Letter::Exact(letter) })
+            if !line.starts_with('(') =>
+        {
+            // line starts with the first number, so the second starts just
+            //   after the '-'
+            number_columns = [1, line.find('-').unwrap() + 2];
+            LinePolicy::Plain(PasswordPolicy { first_ix: min, second_ix: max, letter })
+        }
+        expr => LinePolicy::Expr(expr),
+    };
+
+    // a position past the end of the password never holds the letter, so
+    //   a Positional using one is pointless
+    let length = options.indexing.length(password);
+    if options.range_check == RangeCheck::Strict {
+        if let Some((ix, column)) = parser.positions.iter().find(|(ix, _)| *ix > length) {
+            return Err(past_the_end(*ix, length, options.indexing).at(line_no, *column));
+        }
+    }
+
+    Ok(InputLine {
+        line_no,
+        policy,
+        password: password.to_string(),
+        indexing: options.indexing,
+        range_check: options.range_check,
+        number_columns,
+    })
+}
+
+// the error for a position past the end of a length-unit password
+fn past_the_end(ix: usize, length: usize, indexing: Indexing) -> Error {
+    Error::parse(format!(
+        "position {} is past the end of the {}-{} password",
+        ix,
+        length,
+        indexing.name()
+    ))
+}
+
+impl InputLine {
+    // Check this line's policy can be read as the given kind. A plain
+    //   policy's numbers are positions when it's read as a Positional, so
+    //   then they must start at 1 and, checking strictly, be in the password;
+    //   a number past the end almost always means the line is corrupt.
+    //   Read as a count range, any numbers will do.
+    pub fn check_reading(&self, kind: PolicyKind) -> Result<()> {
+        let policy = match (&self.policy, kind) {
+            (LinePolicy::Plain(policy), PolicyKind::Positional) => policy,
+            _ => return Ok(()),
+        };
+
+        let length = self.indexing.length(&self.password);
+        let numbers = [policy.first_ix, policy.second_ix];
+        for (ix, column) in numbers.iter().zip(self.number_columns.iter()) {
+            if *ix == 0 {
+                return Err(Error::parse("positions start at 1").at(self.line_no, *column));
+            }
+            if self.range_check == RangeCheck::Strict && *ix > length {
+                return Err(past_the_end(*ix, length, self.indexing).at(self.line_no, *column));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parse each line of the password database into a policy and a password,
+//   indexing by char, and checking strictly that positions are in their
+//   password
+pub fn parse_input(input: &str) -> Result<Vec<InputLine>> {
+    parse_input_with(input, ParseOptions::default())
+}
+
+// Parse each line of the password database as options says
+pub fn parse_input_with(input: &str, options: ParseOptions) -> Result<Vec<InputLine>> {
+    input::lines(input)
+        .map(|line| parse_line(line.number, line.text, options))
+        .collect()
+}
+
+// Count the passwords each kind of policy accepts, reading every line once.
+//   The counts are in the same order as kinds. Fails if a line can't be read
+//   as one of kinds; see InputLine::check_reading.
+pub fn count_valid(input_lines: &[InputLine], kinds: &[PolicyKind]) -> Result<Vec<usize>> {
+    let mut counts: Vec<usize> = vec![0; kinds.len()];
+    for line in input_lines.iter() {
+        for (kind, count) in kinds.iter().zip(counts.iter_mut()) {
+            line.check_reading(*kind)?;
+            if line.policy.accepts(*kind, &line.password, line.indexing) {
+                *count += 1;
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+// Check every line against each kind of policy, explaining each verdict.
+//   Entries are in line order, with one entry per kind for each line with a
+//   plain policy, and one entry for each line with an expression. Fails as
+//   count_valid does.
+pub fn audit(input_lines: &[InputLine], kinds: &[PolicyKind]) -> Result<Vec<AuditEntry>> {
+    let mut entries: Vec<AuditEntry> = Vec::new();
     for line in input_lines.iter() {
-        let first_char = line.password
-            .chars()
-            .nth(line.policy.first_ix - 1)
-            .unwrap();
-        let second_char = line.password
-            .chars()
-            .nth(line.policy.second_ix - 1)
-            .unwrap();
-        if ((first_char == line.policy.letter) ||
-                (second_char == line.policy.letter))
-            &&
-            (first_char != second_char) {
-                count += 1;
-        }
-    }
-
-    println!("{}", count);
-}
\ No newline at end of file
+        // an expression means the same for every kind, so only check it once
+        let kinds = match line.policy {
+            LinePolicy::Plain(_) => kinds,
+            LinePolicy::Expr(_) => &kinds[..kinds.len().min(1)],
+        };
+
+        for kind in kinds.iter() {
+            line.check_reading(*kind)?;
+            let check = line.policy.check(*kind, &line.password, line.indexing);
+            let (letter_count, first_char, second_char) = match &check.finding {
+                Finding::LetterCount { count, .. } => (Some(*count), None, None),
+                Finding::Positions { found, .. } => (
+                    None,
+                    found.first().and_then(|(_, unit)| unit.clone()),
+                    found.get(1).and_then(|(_, unit)| unit.clone()),
+                ),
+                Finding::Length(_) | Finding::Parts(_) => (None, None, None),
+            };
+            entries.push(AuditEntry {
+                line: line.line_no,
+                policy: match line.policy {
+                    LinePolicy::Plain(_) => kind.name(),
+                    LinePolicy::Expr(_) => "expression",
+                },
+                rule: line.policy.rule(*kind),
+                password: line.password.clone(),
+                valid: check.valid,
+                letter_count,
+                first_char,
+                second_char,
+                reason: check.finding.to_string(),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+// Serialize an audit as pretty-printed JSON
+pub fn audit_to_json(entries: &[AuditEntry]) -> String {
+    output::to_pretty_json(entries)
+}
+
+// Serialize an audit as CSV, with a header row. Fields are quoted when they
+//   need to be, as RFC 4180 describes; missing values are left empty.
+pub fn audit_to_csv(entries: &[AuditEntry]) -> String {
+    let quote = |field: String| {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field
+        }
+    };
+    let optional = |field: Option<String>| field.unwrap_or_default();
+
+    let mut csv = AUDIT_CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+    for entry in entries.iter() {
+        let fields = [
+            entry.line.to_string(),
+            entry.policy.to_string(),
+            entry.rule.clone(),
+            entry.password.clone(),
+            entry.valid.to_string(),
+            optional(entry.letter_count.map(|count| count.to_string())),
+            optional(entry.first_char.clone()),
+            optional(entry.second_char.clone()),
+            entry.reason.clone(),
+        ];
+        let fields: Vec<String> = fields.iter().cloned().map(quote).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+pub struct PasswordPhilosophy;
+
+impl Solution for PasswordPhilosophy {
+    type Input = Vec<InputLine>;
+
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Password Philosophy";
+
+    fn parse(&self, input: &str) -> Result<Vec<InputLine>> {
+        parse_input(input)
+    }
+
+    // part 1: count the passwords valid under the sled rental policy
+    fn part1(&self, input_lines: &Vec<InputLine>) -> Result<Answer> {
+        let counts = count_valid(input_lines, &[PolicyKind::CountRange])?;
+        Answer::try_from(counts[0])
+    }
+
+    // part 2: count the passwords valid under the Official Toboggan
+    //   Corporate Policy
+    fn part2(&self, input_lines: &Vec<InputLine>) -> Result<Answer> {
+        let counts = count_valid(input_lines, &[PolicyKind::Positional])?;
+        Answer::try_from(counts[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // options to parse with, indexing by char
+    fn by_char(range_check: RangeCheck) -> ParseOptions {
+        ParseOptions { range_check, indexing: Indexing::Char }
+    }
+
+    // whether each part's policy accepts a single database line
+    fn is_valid(line: &str) -> (bool, bool) {
+        let input_lines = parse_input(line).unwrap();
+        (
+            PasswordPhilosophy.part1(&input_lines).unwrap() == Answer::Number(1),
+            PasswordPhilosophy.part2(&input_lines).unwrap() == Answer::Number(1),
+        )
+    }
+
+    #[test]
+    fn parse_line_splits_policy_and_password() {
+        let line = parse_line(1, "1-3 a: abcde", by_char(RangeCheck::Strict)).unwrap();
+        assert_eq!(
+            line.policy,
+            LinePolicy::Plain(PasswordPolicy { first_ix: 1, second_ix: 3, letter: "a".to_string() })
+        );
+        assert_eq!(line.password, "abcde");
+    }
+
+    // parse a line with an expression, and check a password against it
+    fn expr_accepts(policy: &str, password: &str) -> bool {
+        let line = parse_line(1, &format!("{}: {}", policy, password), by_char(RangeCheck::Lenient)).unwrap();
+        match line.policy {
+            LinePolicy::Expr(expr) => expr.accepts(password, Indexing::Char),
+            LinePolicy::Plain(_) => panic!("{:?} isn't an expression", policy),
+        }
+    }
+
+    #[test]
+    fn expressions() {
+        assert!(expr_accepts("1-3 a AND 2,9 b", "abcdefghi"));
+        assert!(!expr_accepts("1-3 a AND 2,9 b", "abcdefghb"));
+        assert!(expr_accepts("NOT 1-3 a", "bcd"));
+        // AND binds more tightly than OR
+        assert!(expr_accepts("1-1 x OR 1-1 a AND 1-1 b", "x"));
+        assert!(!expr_accepts("(1-1 x OR 1-1 a) AND 1-1 b", "x"));
+        assert!(expr_accepts("LEN 8-64 AND 1-99 [0-9] AND NOT (0-0 [A-Z])", "hunter2Hunter"));
+        assert!(!expr_accepts("LEN 8-64 AND 1-99 [0-9]", "hunter2"));
+        assert!(expr_accepts("2-2 [^a-c-]", "a-dz"));
+        assert!(expr_accepts("1,2 [-x]", "-a"));
+        assert!(expr_accepts("1-3 [a-]", "a-b"));
+        assert!(expr_accepts("2-2 [!-]", "!-#$"));
+        assert!(!expr_accepts("1-9 [!-]", "\"#$"));
+        assert!(expr_accepts("3-3 [\\]\\^\\-\\\\]", "]^-"));
+        assert!(expr_accepts("2-2 [^\\^]", "^ab"));
+    }
+
+    #[test]
+    fn bracket_before_the_password_is_a_letter() {
+        let line = parse_line(1, "1-3 [: a]b", by_char(RangeCheck::Strict)).unwrap();
+        assert_eq!(line.password, "a]b");
+        assert_eq!(line.policy.rule(PolicyKind::CountRange), "1-3 [");
+    }
+
+    #[test]
+    fn classes_print_as_they_parse() {
+        for written in ["1-3 [\\]a]", "1-3 [a\\^]", "1-3 [^\\^]", "1-3 [!\\-]", "1-3 [\\--z]", "1-3 [\\\\]"].iter() {
+            let line = parse_line(1, &format!("{}: abc", written), by_char(RangeCheck::Strict)).unwrap();
+            assert_eq!(&line.policy.rule(PolicyKind::CountRange), written);
+        }
+        // a class written without escapes prints with them
+        let line = parse_line(1, "1-3 []^-]: abc", by_char(RangeCheck::Strict)).unwrap();
+        assert_eq!(line.policy.rule(PolicyKind::CountRange), "1-3 [\\]\\^\\-]");
+    }
+
+    #[test]
+    fn expressions_print_as_they_parse() {
+        let written = "NOT (1-3 a OR LEN 2-4) AND (1,2 [^a-z_] OR 2,9 b) AND NOT NOT 0-0 (";
+        let line = parse_line(1, &format!("{}: abcdefghi", written), by_char(RangeCheck::Strict)).unwrap();
+        assert_eq!(line.policy.rule(PolicyKind::CountRange), written);
+    }
+
+    #[test]
+    fn expression_errors_point_at_the_problem() {
+        let column = |line: &str| parse_line(1, line, by_char(RangeCheck::Strict)).unwrap_err().column;
+        assert_eq!(column("(1-3 a OR 1,2 b: abc"), Some(16));
+        // "AND:" isn't the keyword, so the policy ends before it
+        assert_eq!(column("1-3 a AND: abc"), Some(7));
+        assert_eq!(column("1-3 [z-a]: abc"), Some(6));
+        assert_eq!(column("1-3 a AND 0,2 b: abc"), Some(11));
+        // positions in an expression must be in the password too
+        assert_eq!(column("1-3 a AND 1,12 b: abc"), Some(13));
+    }
+
+    #[test]
+    fn example_lines() {
+        assert_eq!(is_valid("1-3 a: abcde"), (true, true));
+        assert_eq!(is_valid("1-3 b: cdefg"), (false, false));
+        assert_eq!(is_valid("2-9 c: ccccccccc"), (true, false));
+    }
+
+    #[test]
+    fn counts_can_go_past_the_end() {
+        // read as a count range, the numbers aren't positions, so the
+        //   maximum can be more than the password's length and the minimum 0
+        let input_lines = parse_input("1-9 c: ccc\n0-2 a: bbb\n").unwrap();
+        assert_eq!(PasswordPhilosophy.part1(&input_lines).unwrap(), Answer::Number(2));
+        assert!(PasswordPhilosophy.part2(&input_lines).is_err());
+    }
+
+    #[test]
+    fn count_valid_evaluates_every_kind() {
+        let input_lines = parse_input(include_str!("../examples/day02/example.txt")).unwrap();
+        assert_eq!(count_valid(&input_lines, &PolicyKind::ALL).unwrap(), vec![2, 1]);
+        assert_eq!(count_valid(&input_lines, &[PolicyKind::Positional]).unwrap(), vec![1]);
+        assert_eq!("position".parse::<PolicyKind>().unwrap(), PolicyKind::Positional);
+    }
+
+    #[test]
+    fn audit_explains_each_verdict() {
+        let input_lines = parse_input("1-3 b: cdefg\n1-3 a: a,cde\n").unwrap();
+        let entries = audit(&input_lines, &PolicyKind::ALL).unwrap();
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(entries[0].rule, "1-3 b");
+        assert!(!entries[0].valid);
+        assert_eq!(entries[0].letter_count, Some(0));
+        assert_eq!(entries[0].reason, "'b' appears 0 times");
+
+        assert_eq!((entries[3].line, entries[3].policy), (2, "position"));
+        assert!(entries[3].valid);
+        assert_eq!(entries[3].first_char.as_deref(), Some("a"));
+        assert_eq!(entries[3].second_char.as_deref(), Some("c"));
+
+        let csv = audit_to_csv(&entries);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], AUDIT_CSV_HEADER.join(","));
+        assert_eq!(
+            rows[4],
+            "2,position,\"1,3 a\",\"a,cde\",true,,a,c,position 1 is 'a'; position 3 is 'c'; 1 of them is 'a'"
+        );
+    }
+
+    #[test]
+    fn zero_position_is_rejected() {
+        // a count can be 0, but a position can't
+        let line = parse_line(4, "0-3 a: abcde", by_char(RangeCheck::Lenient)).unwrap();
+        assert!(line.check_reading(PolicyKind::CountRange).is_ok());
+        let err = line.check_reading(PolicyKind::Positional).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(4), Some(1)));
+        let line = parse_line(4, "1-0 a: abcde", by_char(RangeCheck::Lenient)).unwrap();
+        assert_eq!(line.check_reading(PolicyKind::Positional).unwrap_err().column, Some(3));
+    }
+
+    #[test]
+    fn malformed_lines_point_at_the_problem() {
+        let column = |line: &str| parse_line(1, line, by_char(RangeCheck::Strict)).unwrap_err().column;
+        assert_eq!(column("1-3a: abcde"), Some(4));
+        assert_eq!(column("1 3 a: abcde"), Some(2));
+        assert_eq!(column("1-3 ab: abcde"), Some(6));
+        assert_eq!(column("1-3 a"), Some(6));
+        assert_eq!(column("x-3 a: abcde"), Some(1));
+        assert_eq!(column("1-99999999999999999999 a: abcde"), Some(3));
+    }
+
+    #[test]
+    fn positions_past_the_end() {
+        let input_lines = parse_input("1-9 é: ébc\n").unwrap();
+        let err = count_valid(&input_lines, &[PolicyKind::Positional]).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(3)));
+        assert!(audit(&input_lines, &PolicyKind::ALL).is_err());
+
+        // leniently, position 9 just doesn't hold the letter
+        let input_lines = parse_input_with("1-9 é: ébc\n2-9 b: ébc\n", by_char(RangeCheck::Lenient)).unwrap();
+        assert_eq!(input_lines[0].policy.rule(PolicyKind::Positional), "1,9 é");
+        assert_eq!(count_valid(&input_lines, &[PolicyKind::Positional]).unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn indexing_by_grapheme() {
+        // "e\u{301}" is an e with a combining acute accent: two chars, three
+        //   bytes, but one grapheme
+        let password = "e\u{301}xe\u{301}";
+        assert_eq!(
+            Indexing::ALL.iter().map(|ix| ix.length(password)).collect::<Vec<_>>(),
+            vec![7, 5, 3]
+        );
+
+        let options = ParseOptions { range_check: RangeCheck::Strict, indexing: Indexing::Grapheme };
+        let input_lines = parse_input_with(&format!("1-3 e\u{301}: {}", password), options).unwrap();
+        assert_eq!(count_valid(&input_lines, &PolicyKind::ALL).unwrap(), vec![1, 0]);
+        let entries = audit(&input_lines, &[PolicyKind::Positional]).unwrap();
+        assert_eq!(entries[0].second_char.as_deref(), Some("e\u{301}"));
+
+        // a skin tone modifier doesn't make a thumbs up a different letter
+        let input_lines =
+            parse_input_with("1,2 \u{1F44D}\u{1F3FD}: \u{1F44D}\u{1F3FD}x", options).unwrap();
+        assert_eq!(count_valid(&input_lines, &[PolicyKind::Positional]).unwrap(), vec![1]);
+        // by char, the letter is just the thumbs up, and the modifier is in the way
+        let err = parse_line(1, "1,2 \u{1F44D}\u{1F3FD}: ab", by_char(RangeCheck::Lenient)).unwrap_err();
+        assert_eq!(err.column, Some(9));
+
+        // position 3 is past the end of a 3-char password that's 2 graphemes
+        let err = parse_input_with("1,3 a: ae\u{301}", options).unwrap_err();
+        assert_eq!(err.column, Some(3));
+        assert!(parse_input_with("1,3 a: ae\u{301}", by_char(RangeCheck::Strict)).is_ok());
+    }
+
+    #[test]
+    fn byte_indexing_needs_single_byte_letters() {
+        let options = ParseOptions { range_check: RangeCheck::Strict, indexing: Indexing::Byte };
+        let err = parse_line(1, "1-3 \u{e9}: abc", options).unwrap_err();
+        assert_eq!(err.column, Some(5));
+        // "é" is two bytes, so the first byte of "éa" isn't the 'a' at position 3
+        let input_lines = parse_input_with("3,4 a: \u{e9}ab", options).unwrap();
+        assert_eq!(count_valid(&input_lines, &[PolicyKind::Positional]).unwrap(), vec![1]);
+    }
+}
@@ -0,0 +1,56 @@
+/******************************************************************************/
+/* Registry: the table of every implemented day                               */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+use crate::binary_boarding::BinaryBoarding;
+use crate::custom_customs::CustomCustoms;
+use crate::handheld_halting::HandheldHalting;
+use crate::handy_haversacks::HandyHaversacks;
+use crate::passport_processing::PassportProcessing;
+use crate::password_philosophy::PasswordPhilosophy;
+use crate::report_repair::ReportRepair;
+use crate::solution::Puzzle;
+use crate::toboggan_trajectory::TobogganTrajectory;
+
+/******************************************************************************/
+/* Structure definitions                                                      */
+/******************************************************************************/
+// Entry: one day in the registry.
+// nickname: the short name the runner has always accepted for this day
+pub struct Entry {
+    pub nickname: &'static str,
+    pub puzzle: &'static dyn Puzzle,
+}
+
+/******************************************************************************/
+/* Constant definitions                                                       */
+/******************************************************************************/
+// REGISTRY: every implemented day, in calendar order
+pub static REGISTRY: [Entry; 8] = [
+    Entry { nickname: "report",   puzzle: &ReportRepair },
+    Entry { nickname: "password", puzzle: &PasswordPhilosophy },
+    Entry { nickname: "toboggan", puzzle: &TobogganTrajectory },
+    Entry { nickname: "passport", puzzle: &PassportProcessing },
+    Entry { nickname: "binary",   puzzle: &BinaryBoarding },
+    Entry { nickname: "custom",   puzzle: &CustomCustoms },
+    Entry { nickname: "handy",    puzzle: &HandyHaversacks },
+    Entry { nickname: "handheld", puzzle: &HandheldHalting },
+];
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Look up a day by name. Accepts the day number ("8"), the day number with a
+//   "day" prefix ("day08" or "day8"), or the day's nickname ("handheld").
+pub fn lookup(name: &str) -> Option<&'static Entry> {
+    let name = name.trim().to_lowercase();
+    let number = name.strip_prefix("day").unwrap_or(&name);
+
+    if let Ok(day) = number.parse::<u32>() {
+        return REGISTRY.iter().find(|entry| entry.puzzle.day() == day);
+    }
+
+    REGISTRY.iter().find(|entry| entry.nickname == name)
+}
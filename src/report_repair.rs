@@ -1,39 +1,895 @@
-use std::io::prelude::*;
-use std::fs::File;
+/******************************************************************************/
+/* Advent of Code 2020 day 1:                                                 */
+/*   -- Report Repair --                                                      */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+#[cfg(feature = "bigint")]
+extern crate num_bigint;
 
-pub fn run(input: &str) {
-    // todo: error handling for missing file
-    let mut file = File::open(input).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-        
-    let mut numbers: Vec<i32> = Vec::new();
-    for s in contents.lines() {
-        numbers.push(s.parse::<i32>().unwrap());
+use crate::error::{Error, ErrorKind, Result};
+use crate::input;
+use crate::solution::{Answer, Solution};
+#[cfg(feature = "bigint")]
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter;
+use std::ops::{Add, Range, Sub};
+use std::str::FromStr;
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+
+/******************************************************************************/
+/* Constant definitions                                                       */
+/******************************************************************************/
+// the sum the elves are looking for
+pub const TARGET: i64 = 2020;
+
+// the most work subset_sum will take on, in entries times possible sums
+pub const SUBSET_SUM_LIMIT: i128 = 100_000_000;
+// the most possible sums subset_sum will make a table for. The table needs 4
+//   bytes per sum, so this bounds its memory to 40MB, even when a few large
+//   entries keep the work under SUBSET_SUM_LIMIT.
+pub const SUBSET_SUM_WIDTH_LIMIT: i128 = 10_000_000;
+
+/******************************************************************************/
+/* Trait definitions                                                          */
+/******************************************************************************/
+// Amount: a type that expense report entries can be read as.
+// Entries are added up as Self::Sum, which is wide enough that adding up any
+//   reasonable number of entries can't overflow.
+pub trait Amount: Clone + Ord + fmt::Display + FromStr {
+    type Sum: Clone + Ord + Add<Output = Self::Sum> + Sub<Output = Self::Sum>;
+
+    fn to_sum(&self) -> Self::Sum;
+    fn zero_sum() -> Self::Sum;
+    fn one() -> Self;
+    // multiply two amounts, or None if the result doesn't fit
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    // the amount as an i128, or None if it doesn't fit
+    fn to_i128(&self) -> Option<i128>;
+}
+
+macro_rules! impl_amount {
+    ($amount:ty, $sum:ty) => {
+        impl Amount for $amount {
+            type Sum = $sum;
+
+            fn to_sum(&self) -> $sum {
+                *self as $sum
+            }
+
+            fn zero_sum() -> $sum {
+                0
+            }
+
+            fn one() -> Self {
+                1
+            }
+
+            fn checked_mul(&self, other: &Self) -> Option<Self> {
+                <$amount>::checked_mul(*self, *other)
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                Some(*self as i128)
+            }
+        }
+    };
+}
+
+impl_amount!(i32, i64);
+impl_amount!(i64, i128);
+
+// big integers can't overflow, so they're their own sum
+#[cfg(feature = "bigint")]
+impl Amount for BigInt {
+    type Sum = BigInt;
+
+    fn to_sum(&self) -> BigInt {
+        self.clone()
+    }
+
+    fn zero_sum() -> BigInt {
+        BigInt::from(0)
+    }
+
+    fn one() -> Self {
+        BigInt::from(1)
     }
 
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        i128::try_from(self).ok()
+    }
+}
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// Combination: some entries of an expense report, and where they are in it.
+// indices[i] is the 0-based index of entries[i] in the slice that was searched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Combination<T = i64> {
+    pub entries: Vec<T>,
+    pub indices: Vec<usize>,
+}
+
+impl<T: Amount> Combination<T> {
+    // Add the entries together
+    pub fn sum(&self) -> T::Sum {
+        self.entries.iter().fold(T::zero_sum(), |sum, entry| sum + entry.to_sum())
+    }
+
+    // Multiply the entries together, or None if the product overflows
+    pub fn product(&self) -> Option<T> {
+        self.entries.iter().try_fold(T::one(), |product, entry| product.checked_mul(entry))
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Combination<i64> {
+    // Multiply the entries together exactly, however big the product gets
+    pub fn big_product(&self) -> BigInt {
+        self.entries.iter().map(|&entry| BigInt::from(entry)).product()
+    }
+}
+
+// SortedReport: an expense report's entries sorted by value, remembering
+//   where each one came from.
+struct SortedReport<'a, T: Amount> {
+    entries: &'a [T],
+    // order[pos] is the index in entries of the pos'th smallest entry
+    order: Vec<usize>,
+    sorted: Vec<T::Sum>,
+}
+
+// Tail: the last one or two entries of a set of combinations that share
+//   all of their other entries. Listing the tail as ranges of sorted
+//   positions lets us count combinations without building each one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Tail {
+    // nothing more is needed
+    Empty,
+    // any one of these positions
+    One(Range<usize>),
+    // one position from low and one from high, which don't overlap
+    Pair { low: Range<usize>, high: Range<usize> },
+    // two positions from within one run of equal entries
+    SamePair(Range<usize>),
+}
+
+impl<'a, T: Amount> SortedReport<'a, T> {
+    fn new(entries: &'a [T]) -> Self {
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        order.sort_by(|&a, &b| entries[a].cmp(&entries[b]));
+        let sorted = order.iter().map(|&ix| entries[ix].to_sum()).collect();
+        SortedReport { entries, order, sorted }
+    }
+
+    // Add up some sums
+    fn sum_of<'s>(sums: impl Iterator<Item = &'s T::Sum>) -> T::Sum
+    where
+        T::Sum: 's,
+    {
+        sums.fold(T::zero_sum(), |total, sum| total + sum.clone())
+    }
+
+    // Build the combination of the entries at the given sorted positions
+    fn combination(&self, positions: &[usize]) -> Combination<T> {
+        Combination {
+            entries: positions.iter().map(|&pos| self.entries[self.order[pos]].clone()).collect(),
+            indices: positions.iter().map(|&pos| self.order[pos]).collect(),
+        }
+    }
+
+    // The end of the run of entries equal to sorted[pos]
+    fn run_end(&self, pos: usize) -> usize {
+        pos + self.sorted[pos..].iter().take_while(|&value| *value == self.sorted[pos]).count()
+    }
+
+    // The start of the run of entries equal to sorted[pos]
+    fn run_start(&self, pos: usize) -> usize {
+        pos - self.sorted[..pos].iter().rev().take_while(|&value| *value == self.sorted[pos]).count()
+    }
+
+    // Search sorted[start..] for count entries summing to target. Each set
+    //   of combinations found is passed to visit along with the positions
+    //   chosen so far, in ascending order; visit returns false to stop the
+    //   search early.
+    // If allow_reuse is set, one position may be chosen more than once.
+    // Returns false if the search was stopped early.
+    fn search<F>(
+        &self,
+        start: usize,
+        target: T::Sum,
+        count: usize,
+        allow_reuse: bool,
+        chosen: &mut Vec<usize>,
+        visit: &mut F,
+    ) -> bool
+    where
+        F: FnMut(&[usize], Tail) -> bool,
+    {
+        let sorted = &self.sorted;
+        match count {
+            0 if target == T::zero_sum() => visit(chosen, Tail::Empty),
+            0 => true,
+            // the run of entries equal to target, found by binary search
+            1 => {
+                let remaining = &sorted[start.min(sorted.len())..];
+                let low = start + remaining.partition_point(|value| *value < target);
+                let high = start + remaining.partition_point(|value| *value <= target);
+                low == high || visit(chosen, Tail::One(low..high))
+            }
+            // two pointers closing in from either end: too small a sum means
+            //   the low entry can't be part of any answer, and vice versa
+            2 => {
+                if start >= sorted.len() {
+                    return true;
+                }
+                let (mut low, mut high) = (start, sorted.len() - 1);
+                while low < high || (allow_reuse && low == high) {
+                    let sum = sorted[low].clone() + sorted[high].clone();
+                    if sum < target {
+                        low += 1;
+                    } else if sum > target {
+                        if high == low {
+                            break;
+                        }
+                        high -= 1;
+                    } else if sorted[low] == sorted[high] {
+                        // every entry from low to high is equal, so any two
+                        //   of them will do
+                        return visit(chosen, Tail::SamePair(low..high + 1));
+                    } else {
+                        let (low_end, high_start) = (self.run_end(low), self.run_start(high));
+                        let tail = Tail::Pair { low: low..low_end, high: high_start..high + 1 };
+                        if !visit(chosen, tail) {
+                            return false;
+                        }
+                        low = low_end;
+                        high = high_start - 1;
+                    }
+                }
+                true
+            }
+            // fix the smallest entry, then look for the rest among the larger
+            //   ones
+            _ => {
+                let last = match sorted.len().checked_sub(1) {
+                    Some(last) => last,
+                    None => return true,
+                };
+                for first in start..sorted.len() {
+                    // the smallest and largest sums of count entries that
+                    //   start with this one
+                    let (min, max) = if allow_reuse {
+                        (
+                            Self::sum_of(iter::repeat_n(&sorted[first], count)),
+                            sorted[first].clone()
+                                + Self::sum_of(iter::repeat_n(&sorted[last], count - 1)),
+                        )
+                    } else {
+                        if first + count > sorted.len() {
+                            break;
+                        }
+                        (
+                            Self::sum_of(sorted[first..first + count].iter()),
+                            sorted[first].clone()
+                                + Self::sum_of(sorted[sorted.len() + 1 - count..].iter()),
+                        )
+                    };
+                    // the smallest sum only grows from here
+                    if min > target {
+                        break;
+                    }
+                    if max < target {
+                        continue;
+                    }
+
+                    chosen.push(first);
+                    let next = if allow_reuse { first } else { first + 1 };
+                    let keep_going = self.search(
+                        next,
+                        target.clone() - sorted[first].clone(),
+                        count - 1,
+                        allow_reuse,
+                        chosen,
+                        visit,
+                    );
+                    chosen.pop();
+                    if !keep_going {
+                        return false;
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    // Search sorted[start..] for count entries whose sum is as close as
+    //   possible to target. Whenever a combination is found that's closer
+    //   than best, best is replaced with its distance from target and its
+    //   positions. Stops early once an exact match is found.
+    fn closest(
+        &self,
+        start: usize,
+        target: T::Sum,
+        count: usize,
+        chosen: &mut Vec<usize>,
+        best: &mut Option<(T::Sum, Vec<usize>)>,
+    ) {
+        let sorted = &self.sorted;
+        if sorted.len() < start + count {
+            return;
+        }
+
+        // replace best if sum is closer to the target
+        let consider = |best: &mut Option<(T::Sum, Vec<usize>)>,
+                        sum: T::Sum,
+                        chosen: &[usize],
+                        tail: &[usize]| {
+            let distance = if sum > target { sum - target.clone() } else { target.clone() - sum };
+            if best.as_ref().is_none_or(|(best_distance, _)| distance < *best_distance) {
+                *best = Some((distance, [chosen, tail].concat()));
+            }
+        };
+
+        match count {
+            0 => consider(best, T::zero_sum(), chosen, &[]),
+            // the closest entry is on one side or the other of where the
+            //   target would go
+            1 => {
+                let after = start + sorted[start..].partition_point(|value| *value < target);
+                if after < sorted.len() {
+                    consider(best, sorted[after].clone(), chosen, &[after]);
+                }
+                if after > start {
+                    consider(best, sorted[after - 1].clone(), chosen, &[after - 1]);
+                }
+            }
+            // two pointers, as in search, visit every pair that could be the
+            //   closest
+            2 => {
+                let (mut low, mut high) = (start, sorted.len() - 1);
+                while low < high {
+                    let sum = sorted[low].clone() + sorted[high].clone();
+                    consider(best, sum.clone(), chosen, &[low, high]);
+                    if sum < target {
+                        low += 1;
+                    } else if sum > target {
+                        high -= 1;
+                    } else {
+                        return;
+                    }
+                }
+            }
+            _ => {
+                for first in start..=sorted.len() - count {
+                    let min = Self::sum_of(sorted[first..first + count].iter());
+                    // every later combination sums to more than this, so
+                    //   this is the closest of them
+                    if min > target {
+                        let smallest: Vec<usize> = (first..first + count).collect();
+                        consider(best, min, chosen, &smallest);
+                        return;
+                    }
+                    let largest_start = sorted.len() + 1 - count;
+                    let max = sorted[first].clone() + Self::sum_of(sorted[largest_start..].iter());
+                    // the closest combination starting here is the largest
+                    if max < target {
+                        let mut largest: Vec<usize> = vec![first];
+                        largest.extend(largest_start..sorted.len());
+                        consider(best, max, chosen, &largest);
+                        continue;
+                    }
+
+                    chosen.push(first);
+                    let rest = target.clone() - sorted[first].clone();
+                    self.closest(first + 1, rest, count - 1, chosen, best);
+                    chosen.pop();
+
+                    if let Some((distance, _)) = best {
+                        if *distance == T::zero_sum() {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Tail {
+    // How many combinations this tail stands for
+    fn len(&self, allow_reuse: bool) -> u64 {
+        match self {
+            Tail::Empty => 1,
+            Tail::One(range) => range.len() as u64,
+            Tail::Pair { low, high } => low.len() as u64 * high.len() as u64,
+            Tail::SamePair(range) => {
+                let len = range.len() as u64;
+                if allow_reuse {
+                    len * (len + 1) / 2
+                } else {
+                    len * (len - 1) / 2
+                }
+            }
+        }
+    }
+
+    // Call visit with each set of positions this tail stands for; visit
+    //   returns false to stop
+    fn each<F>(&self, allow_reuse: bool, mut visit: F) -> bool
+    where
+        F: FnMut(&[usize]) -> bool,
+    {
+        match self {
+            Tail::Empty => visit(&[]),
+            Tail::One(range) => range.clone().all(|pos| visit(&[pos])),
+            Tail::Pair { low, high } => low
+                .clone()
+                .all(|low_pos| high.clone().all(|high_pos| visit(&[low_pos, high_pos]))),
+            Tail::SamePair(range) => range.clone().all(|low_pos| {
+                let high_start = if allow_reuse { low_pos } else { low_pos + 1 };
+                (high_start..range.end).all(|high_pos| visit(&[low_pos, high_pos]))
+            }),
+        }
+    }
+}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Parse the expense report into a sorted vec of entries
+pub fn parse_input(input: &str) -> Result<Vec<i64>> {
+    let mut numbers: Vec<i64> = input::integers(input)?;
+
     // sort the vector
-    numbers.sort();
-        
-    // simple, stupid O(n^3) solution
-    // maintain two iterators, start both at the beginning of the list
-    // add each pair of numbers together until you find the answer
-    for low_num in numbers.iter() {
-        let mut done = false;
-        for high_num in numbers.iter() {
-            for mid_num in numbers.iter() {
-                if low_num + mid_num + high_num == 2020 {
-                    println!("found it! {}", low_num * mid_num * high_num);
-                    done = true;
-                    break;
+    numbers.sort_unstable();
+
+    Ok(numbers)
+}
+
+// Call visit with every combination of count entries that sum to target,
+//   until it returns false.
+// Each combination is a different set of entries, listed in ascending order.
+//   If allow_reuse is set, one entry may appear more than once in a
+//   combination; otherwise every entry in a combination is a different line
+//   of the report, though two lines may hold the same value.
+// entries doesn't need to be sorted; the returned indices are into entries as
+//   given.
+pub fn for_each_sum<T, F>(entries: &[T], target: T, count: usize, allow_reuse: bool, mut visit: F)
+where
+    T: Amount,
+    F: FnMut(Combination<T>) -> bool,
+{
+    let report = SortedReport::new(entries);
+    let mut positions: Vec<usize> = Vec::with_capacity(count);
+    report.search(0, target.to_sum(), count, allow_reuse, &mut Vec::new(), &mut |chosen, tail| {
+        tail.each(allow_reuse, |tail_positions| {
+            positions.clear();
+            positions.extend_from_slice(chosen);
+            positions.extend_from_slice(tail_positions);
+            visit(report.combination(&positions))
+        })
+    });
+}
+
+// Find count different entries that sum to target, as for_each_sum does.
+// If there's more than one answer, which one is found is unspecified.
+// Runs in O(n log n) for count <= 2, and O(n^(count - 1)) beyond that.
+pub fn find_sum<T: Amount>(entries: &[T], target: T, count: usize) -> Option<Combination<T>> {
+    let mut found: Option<Combination<T>> = None;
+    for_each_sum(entries, target, count, false, |combination| {
+        found = Some(combination);
+        false
+    });
+    found
+}
+
+// Find every combination of count entries that sum to target, as
+//   for_each_sum does
+pub fn all_sums<T: Amount>(
+    entries: &[T],
+    target: T,
+    count: usize,
+    allow_reuse: bool,
+) -> Vec<Combination<T>> {
+    let mut all: Vec<Combination<T>> = Vec::new();
+    for_each_sum(entries, target, count, allow_reuse, |combination| {
+        all.push(combination);
+        true
+    });
+    all
+}
+
+// Count the combinations that for_each_sum would find, without building
+//   any of them. Runs of equal entries are counted in one step, so this is
+//   fast even when there are vastly more combinations than entries.
+pub fn count_sums<T: Amount>(entries: &[T], target: T, count: usize, allow_reuse: bool) -> u64 {
+    let report = SortedReport::new(entries);
+    let mut total: u64 = 0;
+    report.search(0, target.to_sum(), count, allow_reuse, &mut Vec::new(), &mut |_, tail| {
+        total += tail.len(allow_reuse);
+        true
+    });
+    total
+}
+
+// Find count different entries whose sum is as close as possible to target.
+// If there's a tie, which combination is found is unspecified. Returns None
+//   only if there are fewer than count entries.
+pub fn closest_sum<T: Amount>(entries: &[T], target: T, count: usize) -> Option<Combination<T>> {
+    let report = SortedReport::new(entries);
+    let mut best: Option<(T::Sum, Vec<usize>)> = None;
+    report.closest(0, target.to_sum(), count, &mut Vec::new(), &mut best);
+    best.map(|(_, positions)| report.combination(&positions))
+}
+
+// Find any number of different entries that sum to target, by dynamic
+//   programming over every sum the entries could make.
+// Fails if there are more possible sums than SUBSET_SUM_LIMIT or
+//   SUBSET_SUM_WIDTH_LIMIT allow for, or if an entry doesn't fit in an i128.
+pub fn subset_sum<T: Amount>(entries: &[T], target: T) -> Result<Option<Combination<T>>> {
+    let too_big = || Error::new(ErrorKind::Unsupported, "too many possible sums for subset sum");
+    let values: Vec<i128> = entries
+        .iter()
+        .map(|entry| entry.to_i128().ok_or_else(too_big))
+        .collect::<Result<_>>()?;
+    let target = target.to_i128().ok_or_else(too_big)?;
+
+    // every subset sums to something between the sum of the negative entries
+    //   and the sum of the positive ones
+    //   entries near the limits of an i128 could overflow these, and would
+    //   be far too many sums to try anyway
+    let checked_sum = |keep: fn(i128) -> bool| {
+        values
+            .iter()
+            .filter(|&&value| keep(value))
+            .try_fold(0i128, |sum, &value| sum.checked_add(value))
+            .ok_or_else(too_big)
+    };
+    let lowest = checked_sum(|value| value < 0)?;
+    let highest = checked_sum(|value| value > 0)?;
+    if target < lowest || target > highest {
+        return Ok(None);
+    }
+    let width = highest
+        .checked_sub(lowest)
+        .and_then(|width| width.checked_add(1))
+        .ok_or_else(too_big)?;
+    let work = width.checked_mul(values.len().max(1) as i128).ok_or_else(too_big)?;
+    if width > SUBSET_SUM_WIDTH_LIMIT || work > SUBSET_SUM_LIMIT {
+        return Err(too_big());
+    }
+    // with the table small enough, every sum from here on fits easily
+
+    // reached_by[sum - lowest] is 1 + the index of the entry that first made
+    //   sum, NOT_REACHED if no subset makes it, or EMPTY for the empty subset.
+    //   Entries are tried in order, so the rest of the subset only uses
+    //   entries from before the one recorded here.
+    const NOT_REACHED: u32 = 0;
+    const EMPTY: u32 = u32::MAX;
+    let mut reached_by: Vec<u32> = vec![NOT_REACHED; width as usize];
+    let offset = |sum: i128| (sum - lowest) as usize;
+    reached_by[offset(0)] = EMPTY;
+
+    for (ix, &value) in values.iter().enumerate() {
+        // visit the sums in the order that doesn't reuse this entry: each sum
+        //   is made from one we haven't updated for this entry yet
+        let sums: Box<dyn Iterator<Item = i128>> = if value > 0 {
+            Box::new((lowest + value..=highest).rev())
+        } else if value < 0 {
+            Box::new(lowest..=highest + value)
+        } else {
+            // a zero never makes a new sum
+            continue;
+        };
+        for sum in sums {
+            if reached_by[offset(sum)] == NOT_REACHED
+                && reached_by[offset(sum - value)] != NOT_REACHED
+            {
+                reached_by[offset(sum)] = ix as u32 + 1;
+            }
+        }
+    }
+
+    if reached_by[offset(target)] == NOT_REACHED {
+        return Ok(None);
+    }
+
+    // walk back through the entries that made each sum
+    let mut indices: Vec<usize> = Vec::new();
+    let mut sum = target;
+    while reached_by[offset(sum)] != EMPTY {
+        let ix = reached_by[offset(sum)] as usize - 1;
+        indices.push(ix);
+        sum -= values[ix];
+    }
+    indices.sort_by(|&a, &b| entries[a].cmp(&entries[b]));
+
+    Ok(Some(Combination {
+        entries: indices.iter().map(|&ix| entries[ix].clone()).collect(),
+        indices,
+    }))
+}
+
+// The product of a combination's entries as an answer.
+// If the product overflows an i64, it's given exactly as text when built
+//   with the bigint feature, and is an error otherwise.
+pub fn product_answer(combination: &Combination<i64>) -> Result<Answer> {
+    if let Some(product) = combination.product() {
+        return Ok(Answer::from(product));
+    }
+
+    #[cfg(feature = "bigint")]
+    return Ok(Answer::Text(combination.big_product().to_string()));
+
+    #[cfg(not(feature = "bigint"))]
+    Err(Error::new(
+        ErrorKind::Overflow,
+        format!("the product of {:?} doesn't fit in 64 bits", combination.entries),
+    ))
+}
+
+pub struct ReportRepair;
+
+impl Solution for ReportRepair {
+    type Input = Vec<i64>;
+
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Report Repair";
+
+    fn parse(&self, input: &str) -> Result<Vec<i64>> {
+        parse_input(input)
+    }
+
+    // part 1: find the two entries that sum to 2020 and multiply them
+    fn part1(&self, numbers: &Vec<i64>) -> Result<Answer> {
+        match find_sum(numbers, TARGET, 2) {
+            Some(combination) => product_answer(&combination),
+            None => Err(Error::no_solution(format!("no two entries sum to {}", TARGET))),
+        }
+    }
+
+    // part 2: find the three entries that sum to 2020 and multiply them
+    fn part2(&self, numbers: &Vec<i64>) -> Result<Answer> {
+        match find_sum(numbers, TARGET, 3) {
+            Some(combination) => product_answer(&combination),
+            None => Err(Error::no_solution(format!("no three entries sum to {}", TARGET))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day01/example.txt");
+
+    #[test]
+    fn parse_input_sorts_entries() {
+        assert_eq!(parse_input(EXAMPLE).unwrap(), vec![299, 366, 675, 979, 1456, 1721]);
+    }
+
+    #[test]
+    fn parse_input_reports_the_bad_line() {
+        let err = parse_input("1721\nabc\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn example_pair_and_triple() {
+        let entries = parse_input(EXAMPLE).unwrap();
+        assert_eq!(ReportRepair.part1(&entries).unwrap(), Answer::Number(514579));
+        assert_eq!(ReportRepair.part2(&entries).unwrap(), Answer::Number(241861950));
+    }
+
+    #[test]
+    fn find_sum_reports_original_indices() {
+        let entries: Vec<i64> = input::integers(EXAMPLE).unwrap();
+        let pair = find_sum(&entries, TARGET, 2).unwrap();
+        assert_eq!(pair.entries, vec![299, 1721]);
+        assert_eq!(pair.indices, vec![3, 0]);
+
+        let triple = find_sum(&entries, TARGET, 3).unwrap();
+        assert_eq!(triple.entries, vec![366, 675, 979]);
+        assert_eq!(triple.indices, vec![2, 4, 1]);
+    }
+
+    #[test]
+    fn find_sum_uses_each_entry_once() {
+        // 1010 + 1010 would need the same entry twice
+        assert_eq!(find_sum(&[1010, 5, 7], 2020, 2), None);
+        assert!(find_sum(&[1010, 5, 1010], 2020, 2).is_some());
+    }
+
+    #[test]
+    fn find_sum_of_more_entries() {
+        let entries = [8, -3, 15, 4, 1, 9, 23, 6];
+        let combination = find_sum(&entries, 20, 4).unwrap();
+        assert_eq!(combination.entries, vec![-3, 6, 8, 9]);
+        assert_eq!(combination.indices, vec![1, 7, 0, 5]);
+        // the four smallest entries already sum to more than 2
+        assert_eq!(find_sum(&entries, 2, 4), None);
+    }
+
+    // every set of count indices into entries summing to target, the slow way.
+    //   indices are ascending, and may repeat if allow_reuse is set
+    fn brute_force_sums(entries: &[i32], target: i32, count: usize, allow_reuse: bool) -> Vec<Vec<usize>> {
+        fn extend(
+            entries: &[i32],
+            target: i32,
+            count: usize,
+            allow_reuse: bool,
+            chosen: &mut Vec<usize>,
+            found: &mut Vec<Vec<usize>>,
+        ) {
+            if chosen.len() == count {
+                if chosen.iter().map(|&ix| entries[ix]).sum::<i32>() == target {
+                    found.push(chosen.clone());
                 }
+                return;
             }
-            if done {
-                break;
+            let start = match chosen.last() {
+                Some(&last) if allow_reuse => last,
+                Some(&last) => last + 1,
+                None => 0,
+            };
+            for ix in start..entries.len() {
+                chosen.push(ix);
+                extend(entries, target, count, allow_reuse, chosen, found);
+                chosen.pop();
             }
         }
-        if done {
-            break;
+
+        let mut found = Vec::new();
+        extend(entries, target, count, allow_reuse, &mut Vec::new(), &mut found);
+        found
+    }
+
+    #[test]
+    fn all_sums_matches_brute_force() {
+        let entries = [5, -2, 3, 3, 0, 5, 8, -2, 3, 1, 10, 5];
+        for &allow_reuse in [false, true].iter() {
+            for count in 0..=4 {
+                for target in -6..=20 {
+                    let mut expected = brute_force_sums(&entries, target, count, allow_reuse);
+                    let mut found: Vec<Vec<usize>> = all_sums(&entries, target, count, allow_reuse)
+                        .into_iter()
+                        .map(|combination| {
+                            assert!(combination.entries.windows(2).all(|pair| pair[0] <= pair[1]));
+                            let mut indices = combination.indices;
+                            indices.sort_unstable();
+                            indices
+                        })
+                        .collect();
+                    expected.sort();
+                    found.sort();
+                    assert_eq!(found, expected, "count {} target {} reuse {}", count, target, allow_reuse);
+                    assert_eq!(count_sums(&entries, target, count, allow_reuse), expected.len() as u64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn closest_sum_matches_brute_force() {
+        let entries = [5, -2, 3, 3, 0, 5, 8, -2, 3, 1, 10, 5];
+        for count in 1..=4 {
+            for target in -10..=40 {
+                // the smallest distance of any combination from the target
+                let best = (-20..=40)
+                    .filter(|&sum| !brute_force_sums(&entries, sum, count, false).is_empty())
+                    .map(|sum: i32| (sum - target).abs())
+                    .min()
+                    .unwrap();
+                let combination = closest_sum(&entries, target, count).unwrap();
+                assert_eq!(combination.entries.len(), count);
+                let sum = combination.indices.iter().map(|&ix| entries[ix] as i64).sum::<i64>();
+                assert_eq!(sum, combination.sum());
+                assert_eq!((sum - target as i64).abs(), best as i64, "count {} target {}", count, target);
+            }
         }
+        assert_eq!(closest_sum(&entries, 0, 13), None);
+    }
+
+    #[test]
+    fn closest_sum_of_example() {
+        let entries: Vec<i64> = input::integers(EXAMPLE).unwrap();
+        assert_eq!(closest_sum(&entries, TARGET, 2).unwrap().entries, vec![299, 1721]);
+        // 299 + 1721 is still the closest pair, just 1 under
+        assert_eq!(closest_sum(&entries, TARGET + 1, 2).unwrap().entries, vec![299, 1721]);
+    }
+
+    #[test]
+    fn subset_sum_matches_brute_force() {
+        let entries = [5, -2, 3, 3, 0, 8, -7, 1, 10];
+        for target in -12..=35 {
+            let possible = (0..=entries.len())
+                .any(|count| !brute_force_sums(&entries, target, count, false).is_empty());
+            match subset_sum(&entries, target).unwrap() {
+                Some(combination) => {
+                    assert!(possible, "target {}", target);
+                    assert_eq!(combination.sum(), target as i64);
+                    let mut indices = combination.indices.clone();
+                    indices.sort_unstable();
+                    indices.dedup();
+                    assert_eq!(indices.len(), combination.indices.len());
+                }
+                None => assert!(!possible, "target {}", target),
+            }
+        }
+    }
+
+    #[test]
+    fn subset_sum_of_nothing_is_empty() {
+        let combination = subset_sum(&[4, 9], 0).unwrap().unwrap();
+        assert!(combination.entries.is_empty());
+        assert_eq!(subset_sum::<i64>(&[], 1).unwrap(), None);
+    }
+
+    #[test]
+    fn subset_sum_refuses_huge_tables() {
+        let entries: Vec<i64> = vec![1, 2, 4_000_000_000_000];
+        assert_eq!(subset_sum(&entries, 3).unwrap_err().kind, ErrorKind::Unsupported);
+        // little work, but a table of 99 million sums
+        let entries: Vec<i64> = vec![99_000_000];
+        assert_eq!(subset_sum(&entries, 3).unwrap_err().kind, ErrorKind::Unsupported);
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn subset_sum_near_the_i128_limits_is_refused() {
+        // each of these fits in an i128, but their sums and the gap between
+        //   them don't
+        let entries: Vec<BigInt> = vec![i128::MAX.into(), i128::MAX.into(), i128::MIN.into()];
+        let err = subset_sum(&entries, BigInt::from(3)).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Unsupported);
+        let entries: Vec<BigInt> = vec![i128::MAX.into(), i128::MIN.into()];
+        let err = subset_sum(&entries, BigInt::from(-1)).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn count_sums_of_many_equal_entries() {
+        // 1000 entries of 1010: every pair of them sums to 2020
+        let entries = vec![1010; 1000];
+        assert_eq!(count_sums(&entries, 2020, 2, false), 1000 * 999 / 2);
+        assert_eq!(count_sums(&entries, 2020, 2, true), 1000 * 1001 / 2);
+    }
+
+    #[test]
+    fn product_overflow_is_detected() {
+        let entries: Vec<i64> = vec![4_000_000_000_000, -3_999_999_997_980, 5];
+        let pair = find_sum(&entries, TARGET, 2).unwrap();
+        assert_eq!(pair.product(), None);
+
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(product_answer(&pair).unwrap_err().kind, ErrorKind::Overflow);
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            product_answer(&pair).unwrap(),
+            Answer::Text("-15999999991920000000000000".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_input_accepts_large_and_negative_entries() {
+        let entries = parse_input("9000000000000000000\n-5\n").unwrap();
+        assert_eq!(entries, vec![-5, 9_000_000_000_000_000_000]);
+    }
+
+    #[test]
+    fn no_pair_is_no_solution() {
+        let entries = parse_input("1\n2\n3\n").unwrap();
+        assert_eq!(ReportRepair.part1(&entries).unwrap_err().kind, ErrorKind::NoSolution);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,294 @@
+/******************************************************************************/
+/* Solution: the common interface implemented by every day's puzzle           */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+use crate::bench::{BenchReport, Phase};
+use crate::error::{Error, ErrorKind, Result};
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// Answer: the result of solving one part of a puzzle.
+// Most puzzles have a numeric answer, but some answers are strings, so keep
+//   both around rather than forcing everything through a String.
+// Serializes as a bare number or string.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{}", num),
+            Answer::Text(text)  => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(num: i32) -> Self {
+        Answer::Number(num as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(num: u32) -> Self {
+        Answer::Number(num as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(num: i64) -> Self {
+        Answer::Number(num)
+    }
+}
+
+// u64 and usize answers might not fit in an i64, so converting them can fail
+impl TryFrom<u64> for Answer {
+    type Error = Error;
+
+    fn try_from(num: u64) -> Result<Self> {
+        i64::try_from(num).map(Answer::Number).map_err(|_| answer_overflow(num))
+    }
+}
+
+impl TryFrom<usize> for Answer {
+    type Error = Error;
+
+    fn try_from(num: usize) -> Result<Self> {
+        i64::try_from(num).map(Answer::Number).map_err(|_| answer_overflow(num))
+    }
+}
+
+fn answer_overflow(num: impl fmt::Display) -> Error {
+    Error::new(ErrorKind::Overflow, format!("the answer {} is too big for an i64", num))
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+// Part: one of the two parts of a puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    // both parts, in order
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    // Parse a part selection as given on the command line: "1", "2" or "both"
+    pub fn parse_selection(selection: &str) -> Option<Vec<Part>> {
+        match selection {
+            "1"    => Some(vec![Part::One]),
+            "2"    => Some(vec![Part::Two]),
+            "both" => Some(Part::BOTH.to_vec()),
+            _      => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// PartAnswer: the answer to one part of a puzzle, and how long it took to
+//   find it (not counting parsing)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartAnswer {
+    pub part: Part,
+    pub answer: Result<Answer>,
+    pub time: Duration,
+}
+
+// Solved: the answers to the requested parts of a puzzle, in the order they
+//   were requested.
+// Each part is solved independently, so one part failing doesn't stop us from
+//   reporting the other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solved {
+    // how long it took to parse the input
+    pub parse_time: Duration,
+    pub answers: Vec<PartAnswer>,
+}
+
+impl Solved {
+    // Get the answer to one part, if that part was requested
+    pub fn get(&self, part: Part) -> Option<&PartAnswer> {
+        self.answers.iter().find(|answer| answer.part == part)
+    }
+
+    // Total time spent parsing and solving
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.answers.iter().map(|answer| answer.time).sum::<Duration>()
+    }
+}
+
+/******************************************************************************/
+/* Trait definitions                                                          */
+/******************************************************************************/
+// Solution: implemented by each day.
+// The puzzle input is parsed once into Self::Input, and then both parts are
+//   solved from the parsed input.
+pub trait Solution {
+    // the parsed representation of the puzzle input
+    type Input;
+
+    // day of the advent calendar, starting at 1
+    const DAY: u32;
+    // the puzzle's title, e.g. "Report Repair"
+    const TITLE: &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer>;
+
+    fn solve_part(&self, input: &Self::Input, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+// Puzzle: a type-erased Solution, so that every day can be stored in one
+//   table regardless of its Input type.
+// This is implemented automatically for every Solution.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    // Parse the input and solve the given parts. Fails only if the input
+    //   can't be parsed; failures in either part, including panics, are
+    //   reported in the returned Solved.
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Solved>;
+    // Parse the input and solve the given parts iterations times each,
+    //   timing every phase separately
+    fn bench(&self, input: &str, parts: &[Part], iterations: usize) -> Result<BenchReport>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Solved> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse_time = start.elapsed();
+
+        let answers = parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+                // a panic in one part shouldn't lose the other's answer
+                let answer = panic::catch_unwind(AssertUnwindSafe(|| self.solve_part(&parsed, *part)))
+                    .unwrap_or_else(|payload| Err(Error::from_panic(payload)));
+                PartAnswer { part: *part, answer, time: start.elapsed() }
+            })
+            .collect();
+
+        Ok(Solved { parse_time, answers })
+    }
+
+    fn bench(&self, input: &str, parts: &[Part], iterations: usize) -> Result<BenchReport> {
+        // parse once up front, so that a bad input fails before we spend any
+        //   time benchmarking
+        let parsed = self.parse(input)?;
+
+        let mut samples: Vec<(Phase, Vec<Duration>)> = Vec::new();
+
+        let mut parse_samples: Vec<Duration> = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            // keep the parsed input alive until after we've stopped the
+            //   clock, so that dropping it isn't timed
+            let _parsed = self.parse(input)?;
+            parse_samples.push(start.elapsed());
+        }
+        samples.push((Phase::Parse, parse_samples));
+
+        for part in parts.iter() {
+            let mut part_samples: Vec<Duration> = Vec::with_capacity(iterations);
+            for _ in 0..iterations {
+                let start = Instant::now();
+                // a part with no answer is still worth timing, so ignore
+                //   the result
+                let _ = self.solve_part(&parsed, *part);
+                part_samples.push(start.elapsed());
+            }
+            samples.push((Phase::from(*part), part_samples));
+        }
+
+        Ok(BenchReport::from_samples(S::DAY, S::TITLE, iterations, samples))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct PanicsInPart1;
+
+    impl Solution for PanicsInPart1 {
+        type Input = ();
+
+        const DAY: u32 = 0;
+        const TITLE: &'static str = "Panics In Part 1";
+
+        fn parse(&self, _input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &()) -> Result<Answer> {
+            panic!("part 1 is broken")
+        }
+
+        fn part2(&self, _input: &()) -> Result<Answer> {
+            Ok(Answer::from(2))
+        }
+    }
+
+    #[test]
+    fn a_panic_only_loses_its_own_part() {
+        let solved = PanicsInPart1.solve("", &Part::BOTH).unwrap();
+        let part1 = solved.get(Part::One).unwrap().answer.clone().unwrap_err();
+        assert_eq!((part1.kind, part1.description.as_str()), (ErrorKind::Panic, "panicked: part 1 is broken"));
+        assert_eq!(solved.get(Part::Two).unwrap().answer, Ok(Answer::Number(2)));
+    }
+
+    #[test]
+    fn answers_too_big_for_an_i64_overflow() {
+        assert_eq!(Answer::try_from(42_u64).unwrap(), Answer::Number(42));
+        assert_eq!(Answer::try_from(u64::MAX).unwrap_err().kind, ErrorKind::Overflow);
+    }
+}
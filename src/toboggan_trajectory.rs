@@ -1,64 +1,704 @@
-use std::io::prelude::*;
-use std::fs::File;
+extern crate rayon;
 
-struct Slope {
+use crate::error::{Error, ErrorKind, Result};
+use crate::grid::{Cell, Grid};
+use crate::image::{Image, Rgb};
+use crate::input;
+use crate::solution::{Answer, Solution};
+use rayon::prelude::*;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+// the slopes part 2 checks
+pub const PART2_SLOPES: [Slope; 5] = [
+    Slope { down: 1, right: 1 },
+    Slope { down: 1, right: 3 },
+    Slope { down: 1, right: 5 },
+    Slope { down: 1, right: 7 },
+    Slope { down: 2, right: 1 },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Square {
+    Open,
+    Tree,
+}
+
+// colours for drawing a map as an image: squares no path visits, then one
+//   colour per slope, used in turn
+const OPEN_COLOUR: Rgb = [255, 255, 255];
+const TREE_COLOUR: Rgb = [34, 110, 54];
+const SLOPE_COLOURS: [Rgb; 6] = [
+    [220, 50, 47],
+    [38, 139, 210],
+    [181, 137, 0],
+    [211, 54, 130],
+    [42, 161, 152],
+    [108, 113, 196],
+];
+
+// Slope: how far the toboggan goes each step. down is always at least 1,
+//   which Slope::new checks, or the toboggan would never reach the bottom;
+//   a negative right goes left.
+// Written as "<right>,<down>", e.g. "3,1" or "-1,2".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Slope {
     down: usize,
-    right: usize,
-}
-
-pub fn run(input: &str) {
-    // constant definitions
-    static TREE_SQUARE: u8 = '#' as u8;
-    let slopes: [Slope; 5] = [
-        Slope { down: 1, right: 1 },
-        Slope { down: 1, right: 3 },
-        Slope { down: 1, right: 5 },
-        Slope { down: 1, right: 7 },
-        Slope { down: 2, right: 1 },
-    ];
-
-    // todo: error handling for missing file
-    let mut file = File::open(input).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-
-    // create a vector of numbers which we will eventually multiply together
-    //    to get our final answer
-    let mut nums_to_mult: Vec<u32> = Vec::new();
-
-    // build the 2d-vector representation of the map
-    let mut map: Vec<Vec<u8>> = Vec::new();
-    for line in contents.lines() {
-        map.push(String::from(line).into_bytes());
-    }
-
-    for slope in slopes.iter() {
-        // we need to count the trees that we hit on our way down the hill
-        let mut tree_count: u32 = 0;
-
-        // now we have a vec where the first dimension represents y axis,
-        //   and the second dimension represents x axis
-        // keep track of the current X position
-        let mut cur_x_pos: usize = 0;
-        // use step_by to skip over rows if necessary
-        for line in map.iter().step_by(slope.down) {
-            // the logical x-axis repeats forever... so mod cur_x_pos by the length
-            //   of actual x-axis given in the input file
-            if let Some(position) = line.get((cur_x_pos as usize) % line.len()) {
-                if *position == TREE_SQUARE {
-                    tree_count += 1;
+    right: isize,
+}
+
+// Edge: what the toboggan does when it reaches the side of the map
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    // the map repeats to the side forever, as in the puzzle
+    Wrap,
+    // the toboggan stops before it would leave the map
+    Stop,
+    // the toboggan stays pinned to the side, and keeps going down
+    Clamp,
+    // the toboggan bounces off the side and heads back the other way
+    Bounce,
+}
+
+// WalkMode: how the toboggan moves around the map.
+// With vertical_wrap, the map repeats downwards too, so the toboggan goes
+//   back to the top after the bottom row, until it's somewhere it's already
+//   been.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalkMode {
+    pub edge: Edge,
+    pub vertical_wrap: bool,
+}
+
+// RaggedRows: what to do with a map whose rows aren't all the same width
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RaggedRows {
+    Reject,
+    // pad short rows with open squares
+    Pad,
+}
+
+// TreeIndex: which squares of a map hold trees, packed one bit per square,
+//   so that many slopes can be counted without going back to the map
+pub struct TreeIndex {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+// Walk: the squares visited going down a width * height map along a slope,
+//   as (x, y), walking as a mode says. It steps from square to square
+//   without remembering where it's been, so following a long walk takes no
+//   memory.
+// When wrapping only to the side, x isn't wrapped, so it runs off the map to
+//   the right (or left) as the map repeats; otherwise it's always on the map.
+struct Walk {
+    width: isize,
+    height: usize,
+    slope: Slope,
+    mode: WalkMode,
+    // how far across the toboggan goes before its x repeats. Bouncing, it
+    //   goes across and back without landing on either edge twice
+    period: isize,
+    // how far across the toboggan has gone, ignoring the edges. Unless the
+    //   map only repeats to the side, this stays within one period
+    across: isize,
+    y: usize,
+    // clamped, where the toboggan was the last time its x changed; once
+    //   it's pinned to the side it's only going round in circles when it
+    //   gets back there
+    pinned: (isize, usize),
+    done: bool,
+}
+
+// SlopeCount: how many trees a slope hits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlopeCount {
+    pub slope: Slope,
+    pub trees: u64,
+}
+
+impl Cell for Square {
+    const EXPECTED: &'static str = "'#' or '.'";
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Square::Open),
+            '#' => Some(Square::Tree),
+            _   => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Square::Open => '.',
+            Square::Tree => '#',
+        }
+    }
+}
+
+impl Edge {
+    pub const ALL: [Edge; 4] = [Edge::Wrap, Edge::Stop, Edge::Clamp, Edge::Bounce];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Edge::Wrap   => "wrap",
+            Edge::Stop   => "stop",
+            Edge::Clamp  => "clamp",
+            Edge::Bounce => "bounce",
+        }
+    }
+}
+
+impl FromStr for Edge {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Edge::ALL
+            .iter()
+            .copied()
+            .find(|edge| edge.name() == name)
+            .ok_or_else(|| Error::parse(format!("unknown edge behaviour {:?}", name)))
+    }
+}
+
+impl Default for WalkMode {
+    // the puzzle's rules
+    fn default() -> Self {
+        WalkMode { edge: Edge::Wrap, vertical_wrap: false }
+    }
+}
+
+impl Slope {
+    pub fn new(right: isize, down: usize) -> Result<Self> {
+        if down == 0 {
+            return Err(Error::parse("a slope must go down at least 1 row"));
+        }
+        Ok(Slope { down, right })
+    }
+
+    pub fn right(&self) -> isize {
+        self.right
+    }
+
+    pub fn down(&self) -> usize {
+        self.down
+    }
+}
+
+impl Walk {
+    fn new(width: usize, height: usize, slope: &Slope, mode: WalkMode) -> Self {
+        let width = width as isize;
+        let period = match mode.edge {
+            Edge::Wrap | Edge::Stop | Edge::Clamp => width,
+            Edge::Bounce => (2 * (width - 1)).max(1),
+        };
+        Walk {
+            width,
+            height,
+            slope: *slope,
+            mode,
+            period,
+            across: 0,
+            y: 0,
+            pinned: (0, 0),
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Walk {
+    type Item = (isize, usize);
+
+    fn next(&mut self) -> Option<(isize, usize)> {
+        if self.done {
+            return None;
+        }
+        let x = match self.mode.edge {
+            Edge::Wrap => self.across,
+            Edge::Stop if self.across < 0 || self.across >= self.width => return None,
+            Edge::Stop | Edge::Clamp => self.across,
+            Edge::Bounce if self.across < self.width => self.across,
+            Edge::Bounce => self.period - self.across,
+        };
+        let square = (x, self.y);
+
+        self.across = match self.mode.edge {
+            Edge::Wrap if !self.mode.vertical_wrap => self.across + self.slope.right,
+            // a step too far to count is off the map, so the next one stops
+            Edge::Stop => self.across.checked_add(self.slope.right).unwrap_or(-1),
+            Edge::Clamp => self.across.saturating_add(self.slope.right).clamp(0, self.width - 1),
+            _ => (self.across + self.slope.right.rem_euclid(self.period)) % self.period,
+        };
+        if self.mode.vertical_wrap {
+            self.y = (self.y + self.slope.down % self.height) % self.height;
+            // each step moves across and down by the same amount around the
+            //   repeating map, so the toboggan can only ever get back to
+            //   somewhere it's been by getting back to where it started
+            self.done = self.across == 0 && self.y == 0;
+            // ...except clamping, where every x past the side lands on it.
+            //   The toboggan stops moving across once it's reached the side,
+            //   and from then on only gets back to where it was pinned
+            if self.mode.edge == Edge::Clamp {
+                if self.across != x {
+                    self.pinned = (self.across, self.y);
+                    self.done = false;
+                } else {
+                    self.done = (self.across, self.y) == self.pinned;
                 }
-                cur_x_pos += slope.right;
+            }
+        } else {
+            match self.y.checked_add(self.slope.down) {
+                Some(y) if y < self.height => self.y = y,
+                _ => self.done = true,
             }
         }
-        nums_to_mult.push(tree_count);
+
+        Some(square)
     }
+}
 
-    // calculate product of tree counts
-    let mut product: u32 = 1;
-    for number in nums_to_mult.iter() {
-        product *= *number;
+impl FromStr for Slope {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let (right, down) = text.split_once(',').ok_or_else(|| {
+            Error::parse(format!("invalid slope {:?}: expected <right>,<down>", text))
+        })?;
+        let right = right.trim().parse::<isize>().map_err(|err| {
+            Error::parse(format!("invalid slope {:?}: bad right step: {}", text, err))
+        })?;
+        let down = down.trim().parse::<usize>().map_err(|err| {
+            Error::parse(format!("invalid slope {:?}: bad down step: {}", text, err))
+        })?;
+        Slope::new(right, down).map_err(|err| {
+            Error::parse(format!("invalid slope {:?}: {}", text, err.description))
+        })
     }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.right, self.down)
+    }
+}
+
+impl TreeIndex {
+    pub fn new(map: &Grid<Square>) -> Self {
+        let words_per_row = map.width().div_ceil(64);
+        let mut bits: Vec<u64> = vec![0; words_per_row * map.height()];
+        for (x, y, square) in map.cells() {
+            if *square == Square::Tree {
+                bits[y * words_per_row + x / 64] |= 1 << (x % 64);
+            }
+        }
+
+        TreeIndex { width: map.width(), height: map.height(), words_per_row, bits }
+    }
+
+    // whether (x, y) holds a tree, for x already wrapped onto the map
+    fn is_tree(&self, x: usize, y: usize) -> bool {
+        self.bits[y * self.words_per_row + x / 64] & (1 << (x % 64)) != 0
+    }
+
+    // Count the trees hit along slope, as count_trees does
+    pub fn count_trees(&self, slope: &Slope) -> u64 {
+        // wrap the step once up front, so that each step only has to wrap
+        //   by subtracting the width at most once
+        let step = slope.right.rem_euclid(self.width as isize) as usize;
+        let mut x: usize = 0;
+        let mut tree_count: u64 = 0;
+        for y in (0..self.height).step_by(slope.down) {
+            if self.is_tree(x, y) {
+                tree_count += 1;
+            }
+            x += step;
+            if x >= self.width {
+                x -= self.width;
+            }
+        }
+
+        tree_count
+    }
+
+    // Count the trees hit along slope, walking as mode says
+    pub fn count_trees_with(&self, slope: &Slope, mode: WalkMode) -> u64 {
+        if mode == WalkMode::default() {
+            return self.count_trees(slope);
+        }
+
+        let width = self.width as isize;
+        Walk::new(self.width, self.height, slope, mode)
+            .filter(|&(x, y)| self.is_tree(x.rem_euclid(width) as usize, y))
+            .count() as u64
+    }
+}
+
+// Parse a slope file: one slope per line, as "<right>,<down>". Blank lines
+//   and lines starting with '#' are ignored.
+pub fn parse_slopes(input: &str) -> Result<Vec<Slope>> {
+    let slopes: Vec<Slope> = input::lines(input)
+        .filter(|line| !line.is_blank() && !line.text.starts_with('#'))
+        .map(|line| line.text.trim().parse().map_err(|err: Error| err.at(line.number, 1)))
+        .collect::<Result<_>>()?;
+    if slopes.is_empty() {
+        return Err(Error::parse("no slopes"));
+    }
+
+    Ok(slopes)
+}
+
+// Parse the map into a grid of squares. The grid rejects empty and ragged
+//   rows, which would leave us nothing (or something uneven) to wrap around
+pub fn parse_input(input: &str) -> Result<Grid<Square>> {
+    parse_input_with(input, RaggedRows::Reject)
+}
 
-    println!("Product of tree counts: {}", product);
-}
\ No newline at end of file
+// Parse the map, padding ragged rows instead of rejecting them if asked
+pub fn parse_input_with(input: &str, ragged: RaggedRows) -> Result<Grid<Square>> {
+    match ragged {
+        RaggedRows::Reject => Grid::parse(input),
+        RaggedRows::Pad    => Grid::parse_padded(input, Square::Open),
+    }
+}
+
+// Count the trees that we hit on our way down the hill along slope
+pub fn count_trees(map: &Grid<Square>, slope: &Slope) -> u64 {
+    let mut tree_count: u64 = 0;
+
+    // keep track of the current X position, which goes negative if the
+    //   slope goes left
+    let mut cur_x_pos: isize = 0;
+    // use step_by to skip over rows if necessary
+    for y in (0..map.height()).step_by(slope.down) {
+        // the logical x-axis repeats forever, so let the grid wrap it
+        if *map.get_wrapping(cur_x_pos, y as isize) == Square::Tree {
+            tree_count += 1;
+        }
+        cur_x_pos += slope.right;
+    }
+
+    tree_count
+}
+
+// Count the trees hit along slope, walking as mode says
+pub fn count_trees_with(map: &Grid<Square>, slope: &Slope, mode: WalkMode) -> u64 {
+    if mode == WalkMode::default() {
+        return count_trees(map, slope);
+    }
+
+    Walk::new(map.width(), map.height(), slope, mode)
+        .filter(|&(x, y)| *map.get_wrapping(x, y as isize) == Square::Tree)
+        .count() as u64
+}
+
+// Count the trees hit along each of slopes, in the same order
+pub fn count_trees_along(map: &Grid<Square>, slopes: &[Slope], mode: WalkMode) -> Vec<u64> {
+    slopes.iter().map(|slope| count_trees_with(map, slope, mode)).collect()
+}
+
+// Multiply together tree counts, or None if the product doesn't fit in a u64
+pub fn tree_product(counts: &[u64]) -> Option<u64> {
+    counts.iter().try_fold(1u64, |product, count| product.checked_mul(*count))
+}
+
+// Count the trees hit along every slope going right 1 to max_right and down
+//   1 to max_down, checking the slopes in parallel. The result is ranked from
+//   fewest trees to most; slopes that tie stay in order of down, then right.
+// Fails if the search is wider or taller than the map: going further than
+//   that in one step is no different from starting over, so all it would do
+//   is take longer.
+pub fn rank_slopes(
+    map: &Grid<Square>,
+    max_right: usize,
+    max_down: usize,
+    mode: WalkMode,
+) -> Result<Vec<SlopeCount>> {
+    if max_right > map.width() || max_down > map.height() {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            format!(
+                "can't search slopes past {},{}, the size of the map",
+                map.width(),
+                map.height()
+            ),
+        ));
+    }
+
+    let index = TreeIndex::new(map);
+    let slopes: Vec<Slope> = (1..=max_down)
+        .flat_map(|down| (1..=max_right).map(move |right| Slope { down, right: right as isize }))
+        .collect();
+
+    let mut ranked: Vec<SlopeCount> = slopes
+        .into_par_iter()
+        .map(|slope| SlopeCount { slope, trees: index.count_trees_with(&slope, mode) })
+        .collect();
+    ranked.sort_by_key(|count| count.trees);
+    Ok(ranked)
+}
+
+// The squares visited going down the map along slope, as (x, y). x isn't
+//   wrapped, so it runs off the map to the right (or left) as the map repeats.
+pub fn path(map: &Grid<Square>, slope: &Slope) -> Vec<(isize, usize)> {
+    path_with(map, slope, WalkMode::default())
+}
+
+// The squares visited going down the map along slope, walking as mode says.
+// x is only off the map when the map repeats to the side but not downwards.
+pub fn path_with(map: &Grid<Square>, slope: &Slope, mode: WalkMode) -> Vec<(isize, usize)> {
+    Walk::new(map.width(), map.height(), slope, mode).collect()
+}
+
+// Lay the map out tiled horizontally as far as any of slopes travels, and
+//   find each slope's path across the tiles.
+// Returns the number of tiles, and each path's squares as (column, row) in
+//   the tiled map.
+fn tiled_paths(
+    map: &Grid<Square>,
+    slopes: &[Slope],
+    mode: WalkMode,
+) -> (usize, Vec<Vec<(usize, usize)>>) {
+    let width = map.width() as isize;
+    let paths: Vec<Vec<(isize, usize)>> =
+        slopes.iter().map(|slope| path_with(map, slope, mode)).collect();
+
+    // the tiles each path starts and ends in; paths that go left need tiles
+    //   before the original one
+    let all_xs = || paths.iter().flatten().map(|(x, _)| x.div_euclid(width));
+    let first_tile = all_xs().min().unwrap_or(0).min(0);
+    let last_tile = all_xs().max().unwrap_or(0).max(0);
+
+    let tiled = paths
+        .into_iter()
+        .map(|path| {
+            path.into_iter()
+                .map(|(x, y)| ((x - first_tile * width) as usize, y))
+                .collect()
+        })
+        .collect();
+    ((last_tile - first_tile + 1) as usize, tiled)
+}
+
+// Draw the map as the puzzle does, tiled as far as the paths go, with the
+//   open squares the slopes visit marked 'O' and the trees they hit 'X'
+pub fn render_ascii(map: &Grid<Square>, slopes: &[Slope], mode: WalkMode) -> String {
+    let (tiles, paths) = tiled_paths(map, slopes, mode);
+    let mut rows: Vec<Vec<char>> = map
+        .rows()
+        .map(|row| row.iter().map(Square::to_char).cycle().take(row.len() * tiles).collect())
+        .collect();
+
+    for (column, y) in paths.into_iter().flatten() {
+        let square = &mut rows[y][column];
+        *square = if *square == Square::Tree.to_char() { 'X' } else { 'O' };
+    }
+
+    rows.into_iter()
+        .map(|row| row.into_iter().chain(Some('\n')).collect::<String>())
+        .collect()
+}
+
+// Draw the map as an image, tiled as render_ascii does, with each square
+//   scale pixels across. Each slope's path gets its own colour, darker where
+//   it hits a tree; where paths cross, the later slope is drawn on top.
+pub fn render_image(map: &Grid<Square>, slopes: &[Slope], mode: WalkMode, scale: usize) -> Image {
+    let (tiles, paths) = tiled_paths(map, slopes, mode);
+    let columns = map.width() * tiles;
+    let mut image = Image::new(columns * scale, map.height() * scale, OPEN_COLOUR);
+
+    for (x, y, square) in map.cells() {
+        if *square == Square::Tree {
+            for tile in 0..tiles {
+                image.fill_block(tile * map.width() + x, y, scale, TREE_COLOUR);
+            }
+        }
+    }
+
+    for (slope_ix, path) in paths.into_iter().enumerate() {
+        let colour = SLOPE_COLOURS[slope_ix % SLOPE_COLOURS.len()];
+        let hit_colour = colour.map(|channel| channel / 2);
+        for (column, y) in path {
+            let square = map.get(column % map.width(), y).unwrap();
+            let colour = if *square == Square::Tree { hit_colour } else { colour };
+            image.fill_block(column, y, scale, colour);
+        }
+    }
+
+    image
+}
+
+pub struct TobogganTrajectory;
+
+impl Solution for TobogganTrajectory {
+    type Input = Grid<Square>;
+
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Toboggan Trajectory";
+
+    fn parse(&self, input: &str) -> Result<Grid<Square>> {
+        parse_input(input)
+    }
+
+    // part 1: count the trees hit going right 3, down 1
+    fn part1(&self, map: &Grid<Square>) -> Result<Answer> {
+        Answer::try_from(count_trees(map, &Slope { down: 1, right: 3 }))
+    }
+
+    // part 2: multiply together the trees hit on each of five slopes
+    fn part2(&self, map: &Grid<Square>) -> Result<Answer> {
+        let counts = count_trees_along(map, &PART2_SLOPES, WalkMode::default());
+        match tree_product(&counts) {
+            Some(product) => Answer::try_from(product),
+            None => Err(Error::new(ErrorKind::Overflow, "the product of tree counts overflowed")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day03/example.txt");
+
+    #[test]
+    fn example_slopes() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let trees = |right, down| count_trees(&map, &Slope { right, down });
+        assert_eq!(trees(1, 1), 2);
+        assert_eq!(trees(3, 1), 7);
+        assert_eq!(trees(5, 1), 3);
+        assert_eq!(trees(7, 1), 4);
+        assert_eq!(trees(1, 2), 2);
+    }
+
+    #[test]
+    fn slopes_can_go_left() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let slopes = parse_slopes("# leftwards\n-3,1\n\n3, 1\n").unwrap();
+        assert_eq!(slopes, vec![Slope { right: -3, down: 1 }, Slope { right: 3, down: 1 }]);
+        // the map mirrored left to right, going right 3, hits the same trees
+        let mirrored: String = EXAMPLE
+            .lines()
+            .map(|line| {
+                let reversed: String = line.chars().rev().collect();
+                // keep the start in the top left corner
+                let (last, rest) = reversed.split_at(reversed.len() - 1);
+                format!("{}{}\n", rest, last)
+            })
+            .collect();
+        let mirrored = parse_input(&mirrored).unwrap();
+        assert_eq!(count_trees(&map, &slopes[0]), count_trees(&mirrored, &slopes[1]));
+    }
+
+    #[test]
+    fn tree_index_matches_the_map() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let index = TreeIndex::new(&map);
+        for down in 1..=12 {
+            for right in -25..=25 {
+                let slope = Slope { down, right };
+                assert_eq!(index.count_trees(&slope), count_trees(&map, &slope), "{}", slope);
+            }
+        }
+    }
+
+    #[test]
+    fn ranked_slopes() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let ranked = rank_slopes(&map, 7, 2, WalkMode::default()).unwrap();
+        assert_eq!(ranked.len(), 14);
+        assert_eq!(ranked.last().unwrap(), &SlopeCount { slope: Slope { down: 1, right: 3 }, trees: 7 });
+        assert_eq!(ranked[0], SlopeCount { slope: Slope { down: 2, right: 5 }, trees: 0 });
+        // ties keep their order
+        assert_eq!(ranked[1].slope, Slope { down: 1, right: 2 });
+        assert_eq!(ranked[2].slope, Slope { down: 2, right: 2 });
+        // the search can't go past the edges of the map
+        assert_eq!(rank_slopes(&map, 11, 11, WalkMode::default()).unwrap().len(), 121);
+        assert!(rank_slopes(&map, 12, 1, WalkMode::default()).is_err());
+        assert!(rank_slopes(&map, 1, 12, WalkMode::default()).is_err());
+    }
+
+    #[test]
+    fn ascii_paths_match_the_puzzle() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let drawn = render_ascii(&map, &[Slope { down: 1, right: 3 }], WalkMode::default());
+        let rows: Vec<&str> = drawn.lines().collect();
+        // the path reaches column 30, so the 11-wide map is tiled 3 times
+        assert_eq!(rows[0], "O.##.........##.........##.......");
+        assert_eq!(rows[1], "#..O#...#..#...#...#..#...#...#..");
+        assert_eq!(rows[2], ".#....X..#..#....#..#..#....#..#.");
+        assert_eq!(drawn.matches('X').count(), 7);
+    }
+
+    #[test]
+    fn leftward_paths_tile_to_the_left() {
+        let map = parse_input("..\n.#\n").unwrap();
+        assert_eq!(render_ascii(&map, &[Slope { down: 1, right: -1 }], WalkMode::default()), "..O.\n.X.#\n");
+
+        let image = render_image(&map, &[Slope { down: 1, right: -1 }], WalkMode::default(), 2);
+        assert_eq!((image.width(), image.height()), (8, 4));
+        assert_eq!(image.get(4, 0), Some(SLOPE_COLOURS[0]));
+        assert_eq!(image.get(3, 3), Some(SLOPE_COLOURS[0].map(|channel| channel / 2)));
+        assert_eq!(image.get(6, 2), Some(TREE_COLOUR));
+    }
+
+    #[test]
+    fn edge_modes() {
+        let map = parse_input("....\n....\n....\n....\n....\n").unwrap();
+        let xs = |edge, vertical_wrap| -> Vec<isize> {
+            let mode = WalkMode { edge, vertical_wrap };
+            path_with(&map, &Slope { down: 1, right: 2 }, mode).iter().map(|(x, _)| *x).collect()
+        };
+        assert_eq!(xs(Edge::Wrap, false), vec![0, 2, 4, 6, 8]);
+        assert_eq!(xs(Edge::Stop, false), vec![0, 2]);
+        assert_eq!(xs(Edge::Clamp, false), vec![0, 2, 3, 3, 3]);
+        assert_eq!(xs(Edge::Bounce, false), vec![0, 2, 2, 0, 2]);
+        // 5 rows down and 2 columns of 4 across repeat after 10 steps
+        assert_eq!(xs(Edge::Wrap, true), vec![0, 2, 0, 2, 0, 2, 0, 2, 0, 2]);
+        // bouncing goes across and back every 3 steps, so repeats after 15
+        assert_eq!(xs(Edge::Bounce, true), [0, 2, 2].repeat(5));
+        // pinned to the side from the third step, then round all 5 rows once
+        assert_eq!(xs(Edge::Clamp, true), vec![0, 2, 3, 3, 3, 3, 3]);
+
+        // the index agrees with the map in every mode
+        let map = parse_input(EXAMPLE).unwrap();
+        let index = TreeIndex::new(&map);
+        for &edge in Edge::ALL.iter() {
+            for &vertical_wrap in [false, true].iter() {
+                let mode = WalkMode { edge, vertical_wrap };
+                for slope in PART2_SLOPES.iter().chain(Some(&Slope { down: 3, right: -4 })) {
+                    assert_eq!(index.count_trees_with(slope, mode), count_trees_with(&map, slope, mode));
+                }
+            }
+        }
+        let stop = WalkMode { edge: Edge::Stop, vertical_wrap: false };
+        assert_eq!(count_trees_with(&map, &Slope { down: 1, right: 3 }, stop), 1);
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected_or_padded() {
+        let err = parse_input("..#\n.#\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+        let map = parse_input_with("..#\n.#\n", RaggedRows::Pad).unwrap();
+        assert_eq!(map.to_string(), "..#\n.#.\n");
+    }
+
+    #[test]
+    fn bad_slopes_are_rejected() {
+        assert!("3,0".parse::<Slope>().is_err());
+        assert!(Slope::new(3, 0).is_err());
+        assert_eq!(Slope::new(-1, 2).unwrap().to_string(), "-1,2");
+        assert!("3".parse::<Slope>().is_err());
+        assert_eq!(parse_slopes("1,1\n1,-2\n").unwrap_err().line, Some(2));
+        assert!(parse_slopes("# nothing here\n").is_err());
+    }
+
+    #[test]
+    fn unexpected_square_is_located() {
+        let err = parse_input("..#\n.x.\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+    }
+}
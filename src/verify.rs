@@ -0,0 +1,175 @@
+/******************************************************************************/
+/* Verify: check every day's answers against a file of known-good answers     */
+/******************************************************************************/
+/******************************************************************************/
+/* Dependencies                                                               */
+/******************************************************************************/
+extern crate toml;
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Part, Puzzle};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/******************************************************************************/
+/* Structure/enum definitions                                                 */
+/******************************************************************************/
+// One day's table in the answers file. Either part may be left out if its
+//   answer isn't known yet.
+#[derive(Debug, Deserialize)]
+struct DayTable {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+}
+
+// ExpectedAnswers: the known-good answer to each day and part.
+// The answers file has one table per day, named "day01", "day02", etc:
+//   [day01]
+//   part1 = 910539
+//   part2 = 116724144
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: BTreeMap<(u32, Part), Answer>,
+}
+
+// Verdict: the outcome of checking one part against its expected answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    // the answer matched
+    Pass,
+    // the part was solved, but got the wrong answer
+    Mismatch { expected: Answer, actual: Answer },
+    // the part couldn't be solved at all
+    Fail(Error),
+    // the part was solved, but there's no expected answer to check it against
+    Unknown(Answer),
+}
+
+impl Verdict {
+    pub fn is_pass(&self) -> bool {
+        matches!(self, Verdict::Pass)
+    }
+}
+
+impl ExpectedAnswers {
+    pub fn get(&self, day: u32, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn insert(&mut self, day: u32, part: Part, answer: Answer) {
+        self.answers.insert((day, part), answer);
+    }
+
+    // Parse the contents of an answers file
+    pub fn parse(contents: &str) -> Result<Self> {
+        let tables: BTreeMap<String, DayTable> = toml::from_str(contents).map_err(toml_error)?;
+
+        let mut expected = ExpectedAnswers::default();
+        for (name, table) in tables.iter() {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|number| number.parse::<u32>().ok())
+                .ok_or_else(|| {
+                    Error::parse(format!("expected a table named like \"day01\", found {:?}", name))
+                })?;
+
+            expected.insert_table(day, name, table)?;
+        }
+
+        Ok(expected)
+    }
+
+    // Parse the answers for a single day, given as top-level keys rather than
+    //   a table, e.g. the sidecar file of an example input:
+    //   part1 = 514579
+    //   part2 = 241861950
+    pub fn parse_day(day: u32, contents: &str) -> Result<Self> {
+        let table: DayTable = toml::from_str(contents).map_err(toml_error)?;
+
+        let mut expected = ExpectedAnswers::default();
+        expected.insert_table(day, &format!("day{:02}", day), &table)?;
+        Ok(expected)
+    }
+
+    // Load an answers file from disk
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+        ExpectedAnswers::parse(&contents).map_err(|err| err.in_file(path))
+    }
+
+    // Load a single day's answers from disk, as parsed by parse_day
+    pub fn load_day(day: u32, path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+        ExpectedAnswers::parse_day(day, &contents).map_err(|err| err.in_file(path))
+    }
+
+    // Insert every answer in one day's table. name is the table's name, for
+    //   error messages.
+    fn insert_table(&mut self, day: u32, name: &str, table: &DayTable) -> Result<()> {
+        for (part, value) in [(Part::One, &table.part1), (Part::Two, &table.part2)].iter() {
+            if let Some(value) = value {
+                self.insert(day, *part, value_to_answer(name, *part, value)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/******************************************************************************/
+/* Subroutines                                                                */
+/******************************************************************************/
+// Convert a TOML syntax error into an Error at the same position
+fn toml_error(err: toml::de::Error) -> Error {
+    let error = Error::parse(format!("invalid answers file: {}", err));
+    match err.line_col() {
+        Some((line, column)) => error.at(line + 1, column + 1),
+        None => error,
+    }
+}
+
+// Convert a value from the answers file into an Answer
+fn value_to_answer(name: &str, part: Part, value: &toml::Value) -> Result<Answer> {
+    match value {
+        toml::Value::Integer(num) => Ok(Answer::Number(*num)),
+        toml::Value::String(text) => Ok(Answer::Text(text.clone())),
+        _ => Err(Error::parse(format!(
+            "{}.part{} must be an integer or a string",
+            name, part
+        ))),
+    }
+}
+
+// Solve the given parts of a puzzle and check each against the expected
+//   answers. If the input can't be parsed, every part fails with that error.
+pub fn verify(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    parts: &[Part],
+    expected: &ExpectedAnswers,
+) -> Vec<(Part, Verdict)> {
+    let solved = match puzzle.solve(input, parts) {
+        Ok(solved) => solved,
+        Err(err) => {
+            return parts.iter().map(|part| (*part, Verdict::Fail(err.clone()))).collect();
+        }
+    };
+
+    solved
+        .answers
+        .into_iter()
+        .map(|part_answer| {
+            let verdict = match (part_answer.answer, expected.get(puzzle.day(), part_answer.part)) {
+                (Err(err), _) => Verdict::Fail(err),
+                (Ok(actual), None) => Verdict::Unknown(actual),
+                (Ok(actual), Some(expected)) if actual == *expected => Verdict::Pass,
+                (Ok(actual), Some(expected)) => Verdict::Mismatch {
+                    expected: expected.clone(),
+                    actual,
+                },
+            };
+            (part_answer.part, verdict)
+        })
+        .collect()
+}
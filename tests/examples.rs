@@ -0,0 +1,95 @@
+/******************************************************************************/
+/* Examples: check every day against the worked examples in examples/        */
+/*   Each example is an input, examples/<day>/<name>.txt, next to a sidecar   */
+/*   file of expected answers, examples/<day>/<name>.toml:                    */
+/*     part1 = 514579                                                         */
+/*     part2 = 241861950                                                      */
+/*   Either part may be left out if the example doesn't apply to it. <day>   */
+/*   is anything the runner accepts as a program name, e.g. "day01".          */
+/******************************************************************************/
+use advent_of_code2020::input::Source;
+use advent_of_code2020::verify::{self, ExpectedAnswers, Verdict};
+use advent_of_code2020::{registry, Part};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const EXAMPLES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
+
+// every example input under EXAMPLES_DIR, sorted so failures are reported in
+//   a stable order
+fn example_inputs() -> Vec<PathBuf> {
+    let mut inputs: Vec<PathBuf> = Vec::new();
+    for day_dir in fs::read_dir(EXAMPLES_DIR).unwrap() {
+        let day_dir = day_dir.unwrap().path();
+        if !day_dir.is_dir() {
+            continue;
+        }
+        for file in fs::read_dir(&day_dir).unwrap() {
+            let file = file.unwrap().path();
+            if file.extension() == Some("txt".as_ref()) {
+                inputs.push(file);
+            }
+        }
+    }
+    inputs.sort();
+    inputs
+}
+
+// check one example, returning a description of each problem with it
+fn check_example(input_path: &Path) -> Vec<String> {
+    let day_name = input_path.parent().unwrap().file_name().unwrap().to_str().unwrap();
+    let entry = match registry::lookup(day_name) {
+        Some(entry) => entry,
+        None => return vec![format!("no program named {:?}", day_name)],
+    };
+
+    let sidecar = input_path.with_extension("toml");
+    let expected = match ExpectedAnswers::load_day(entry.puzzle.day(), &sidecar) {
+        Ok(expected) => expected,
+        Err(err) => return vec![err.to_string()],
+    };
+    let source = Source::File(input_path.to_path_buf());
+    let input = match source.load() {
+        Ok(input) => input,
+        Err(err) => return vec![err.to_string()],
+    };
+
+    let verdicts = verify::verify(entry.puzzle, &input, &Part::BOTH, &expected);
+    if verdicts.iter().all(|(_, verdict)| matches!(verdict, Verdict::Unknown(_))) {
+        return vec![format!("{}: no expected answers", sidecar.display())];
+    }
+
+    verdicts
+        .into_iter()
+        .filter_map(|(part, verdict)| match verdict {
+            // parts without an expected answer aren't checked
+            Verdict::Pass | Verdict::Unknown(_) => None,
+            Verdict::Fail(err) => Some(format!("part {}: {}", part, source.locate(err))),
+            Verdict::Mismatch { expected, actual } => Some(format!(
+                "{}: part {}: expected {}, got {}",
+                source, part, expected, actual
+            )),
+        })
+        .collect()
+}
+
+#[test]
+fn every_example_matches_its_expected_answers() {
+    let inputs = example_inputs();
+    assert!(!inputs.is_empty(), "no examples found in {}", EXAMPLES_DIR);
+
+    let failures: Vec<String> = inputs.iter().flat_map(|input| check_example(input)).collect();
+    assert!(failures.is_empty(), "examples failed:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn every_day_has_an_example() {
+    let inputs = example_inputs();
+    for entry in registry::REGISTRY.iter() {
+        let has_example = inputs.iter().any(|input| {
+            let day_name = input.parent().unwrap().file_name().unwrap().to_str().unwrap();
+            registry::lookup(day_name).map(|found| found.puzzle.day()) == Some(entry.puzzle.day())
+        });
+        assert!(has_example, "day {} has no example", entry.puzzle.day());
+    }
+}